@@ -1,22 +1,26 @@
 use clap::Parser;
 
-use raingame::{Game, Message};
-use raingame::{GameState, WordColor};
+use raingame::{
+    parse_seconds, read_message, spawn_message_reader, vocab_hash, write_message, Game, GameMode,
+    Message, ProtocolError, VocabGenerator,
+};
+use raingame::{AttackKind, Board, GameState, Rating, Target, WordColor};
 
-use chrono::{Utc};
+use chrono::Utc;
 use std::fs;
+use std::time::Duration;
 
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use std::fs::File;
-use std::io::{Write};
-
+use std::io::Write;
 
 use tokio::{
-    io::{AsyncReadExt, AsyncWriteExt},
+    io::AsyncWriteExt,
     net::TcpStream,
     sync::mpsc::{self, error::TryRecvError, Receiver, Sender},
     task::yield_now,
+    time::{interval, sleep, Instant, MissedTickBehavior},
 };
 
 use ncurses::*;
@@ -26,6 +30,18 @@ const WIDTH: i32 = 80;
 
 const DEBUG: bool = false;
 
+// 관전자와 다른 플레이어에게 화면을 보내는 간격 (프레임)
+const BOARD_FRAMES: u32 = 2;
+
+// 상태가 바뀌지 않아도 다시 보내는 간격 (프레임)
+const STATE_FRAMES: u32 = 10;
+
+// 게임 화면 오른쪽 상대 패널 너비
+const PANEL_WIDTH: i32 = 30;
+
+// 재접속 시도 간격
+const RECONNECT_INTERVAL: Duration = Duration::from_secs(1);
+
 #[derive(Parser, Debug)]
 struct Opts {
     // Address of the server to connect to
@@ -35,6 +51,54 @@ struct Opts {
     // Port of the server to connect to
     #[arg(short, long, default_value = "22345")]
     port: String,
+
+    // Player name shown to the opponent (default: $USER)
+    #[arg(short, long)]
+    name: Option<String>,
+
+    // Seconds between pings to the server
+    #[arg(long, default_value = "1.0", value_parser = parse_seconds)]
+    ping_interval: Duration,
+
+    // Seconds without any message before the server is considered dead
+    #[arg(long, default_value = "10.0", value_parser = parse_seconds)]
+    timeout: Duration,
+
+    // Create a room and wait for a friend to join with its code
+    #[arg(long, conflicts_with_all = ["room", "list_rooms", "watch"])]
+    create_room: bool,
+
+    // Hide the created room from the room list
+    #[arg(long, requires = "create_room")]
+    private: bool,
+
+    // Number of players the created room waits for
+    #[arg(long, default_value = "2", requires = "create_room")]
+    players: usize,
+
+    // Split the created room into this many teams (0: free-for-all)
+    #[arg(long, default_value = "0", requires = "create_room")]
+    teams: usize,
+
+    // Password for the created room, or for joining or watching a locked room
+    #[arg(long)]
+    password: Option<String>,
+
+    // Join the room with this code
+    #[arg(short, long, conflicts_with_all = ["list_rooms", "watch"])]
+    room: Option<String>,
+
+    // Print the open rooms on the server and exit
+    #[arg(long, conflicts_with = "watch")]
+    list_rooms: bool,
+
+    // Watch the running match with this code
+    #[arg(short, long)]
+    watch: Option<String>,
+
+    // Show a miniature of the target's board in the opponent panel
+    #[arg(long, conflicts_with_all = ["list_rooms", "watch"])]
+    mini_board: bool,
 }
 
 #[tokio::main]
@@ -44,25 +108,224 @@ async fn main() {
     let addr = format!("{}:{}", opts.host, opts.port);
 
     // 서버 TCP 연결
-    let mut socket = tokio::net::TcpStream::connect(addr)
-        .await
-        .expect("Server should be running");
+    let mut socket = match tokio::net::TcpStream::connect(&addr).await {
+        Ok(socket) => socket,
+        Err(e) => {
+            println!("[Client] Failed to connect to {}: {}", addr, e);
+            return;
+        }
+    };
     if DEBUG {
         println!("[Client] Connected to server");
     }
 
-    // vocab 받기
-    let mut file = tokio::fs::File::create("./vocab.txt").await.unwrap();
-    
-    let size = socket.read_u32().await.unwrap();
-    let mut contents = vec![0; size as usize];
-    socket.read_exact(&mut contents).await.unwrap();
-    
-    file.write_all(&contents).await.unwrap();
-    file.shutdown().await.unwrap();
-    
-    if DEBUG {
-        println!("[Client] Vocab received");
+    // 프로토콜 버전 확인
+    let name = opts.name.unwrap_or_else(default_name);
+    if let Err(e) = write_message(&mut socket, &Message::hello(&name)).await {
+        println!("[Client] Failed to greet server: {}", e);
+        return;
+    }
+    match read_message(&mut socket).await {
+        Ok(Message::Welcome { .. }) => {
+            if DEBUG {
+                println!("[Client] Handshake done");
+            }
+        }
+        Ok(Message::Reject { reason }) => {
+            println!("[Client] Server rejected connection: {}", reason);
+            return;
+        }
+        Ok(msg) => {
+            println!(
+                "[Client] Incompatible server: {}",
+                ProtocolError::Unexpected(msg.kind())
+            );
+            return;
+        }
+        Err(e) => {
+            println!("[Client] Incompatible server: {}", e);
+            return;
+        }
+    }
+
+    // vocab 해시를 받고, 캐시에 없으면 vocab 받기
+    let digest = match read_message(&mut socket).await {
+        Ok(Message::VocabHash { digest }) => digest,
+        Ok(msg) => {
+            println!(
+                "[Client] Failed to receive vocab: {}",
+                ProtocolError::Unexpected(msg.kind())
+            );
+            return;
+        }
+        Err(e) => {
+            println!("[Client] Failed to receive vocab: {}", e);
+            return;
+        }
+    };
+
+    let vocab_contents = match read_cached_vocab(&digest) {
+        Some(contents) => {
+            let request = Message::VocabRequest { send: false };
+            if let Err(e) = write_message(&mut socket, &request).await {
+                println!("[Client] Failed to request vocab: {}", e);
+                return;
+            }
+            if DEBUG {
+                println!("[Client] Vocab loaded from cache");
+            }
+            contents
+        }
+        None => {
+            let request = Message::VocabRequest { send: true };
+            if let Err(e) = write_message(&mut socket, &request).await {
+                println!("[Client] Failed to request vocab: {}", e);
+                return;
+            }
+            let contents = match read_message(&mut socket).await {
+                Ok(Message::Vocab { contents }) => contents,
+                Ok(msg) => {
+                    println!(
+                        "[Client] Failed to receive vocab: {}",
+                        ProtocolError::Unexpected(msg.kind())
+                    );
+                    return;
+                }
+                Err(e) => {
+                    println!("[Client] Failed to receive vocab: {}", e);
+                    return;
+                }
+            };
+            write_cached_vocab(&digest, &contents);
+            if DEBUG {
+                println!("[Client] Vocab received");
+            }
+            contents
+        }
+    };
+    let vocab_generator = match VocabGenerator::from_contents(&vocab_contents) {
+        Ok(vocab_generator) => vocab_generator,
+        Err(e) => {
+            println!("[Client] Failed to load vocab: {}", e);
+            return;
+        }
+    };
+
+    // 게임 모드 받기 (비어 있으면 기본 게임)
+    let contents = match read_message(&mut socket).await {
+        Ok(Message::Mode { contents }) => contents,
+        Ok(msg) => {
+            println!(
+                "[Client] Failed to receive game mode: {}",
+                ProtocolError::Unexpected(msg.kind())
+            );
+            return;
+        }
+        Err(e) => {
+            println!("[Client] Failed to receive game mode: {}", e);
+            return;
+        }
+    };
+    let mode = if contents.is_empty() {
+        GameMode::default()
+    } else {
+        match GameMode::parse(&contents) {
+            Ok(mode) => mode,
+            Err(e) => {
+                println!("[Client] Invalid game mode from server: {}", e);
+                return;
+            }
+        }
+    };
+
+    // 로비 요청 (옵션이 없으면 빠른 매치)
+    let request = if opts.list_rooms {
+        Message::ListRooms
+    } else if opts.create_room {
+        Message::CreateRoom {
+            private: opts.private,
+            password: opts.password,
+            size: opts.players,
+            teams: opts.teams,
+        }
+    } else if let Some(code) = opts.room {
+        Message::JoinRoom {
+            code,
+            password: opts.password,
+        }
+    } else if let Some(code) = opts.watch.clone() {
+        Message::Watch {
+            code,
+            password: opts.password,
+        }
+    } else {
+        Message::QuickMatch
+    };
+    if let Err(e) = write_message(&mut socket, &request).await {
+        println!("[Client] Failed to send lobby request: {}", e);
+        return;
+    }
+
+    // 방 목록만 출력하고 종료
+    if opts.list_rooms {
+        match read_message(&mut socket).await {
+            Ok(Message::Rooms { rooms }) => {
+                if rooms.is_empty() {
+                    println!("No open rooms");
+                }
+                for room in rooms {
+                    let teams = if room.teams > 0 {
+                        format!(" ({} teams)", room.teams)
+                    } else {
+                        String::new()
+                    };
+                    println!(
+                        "{}  {}  {}/{}{}{}{}",
+                        room.code,
+                        room.host,
+                        room.players,
+                        room.size,
+                        teams,
+                        if room.locked { " (password)" } else { "" },
+                        if room.playing { " (playing)" } else { "" }
+                    );
+                }
+            }
+            Ok(msg) => {
+                println!(
+                    "[Client] Failed to list rooms: {}",
+                    ProtocolError::Unexpected(msg.kind())
+                );
+            }
+            Err(e) => {
+                println!("[Client] Failed to list rooms: {}", e);
+            }
+        }
+        return;
+    }
+
+    // 진행 중인 매치 관전
+    if opts.watch.is_some() {
+        let (players, teams) = match read_message(&mut socket).await {
+            Ok(Message::Spectate { players, teams }) => (players, teams),
+            Ok(Message::Reject { reason }) => {
+                println!("[Client] Server rejected watch request: {}", reason);
+                return;
+            }
+            Ok(msg) => {
+                println!(
+                    "[Client] Failed to watch match: {}",
+                    ProtocolError::Unexpected(msg.kind())
+                );
+                return;
+            }
+            Err(e) => {
+                println!("[Client] Failed to watch match: {}", e);
+                return;
+            }
+        };
+        spawn_spectator(socket, players, teams).await;
+        return;
     }
 
     // 채널 생성`
@@ -70,12 +333,9 @@ async fn main() {
     let (game_writer, game_reader) = mpsc::channel::<Message>(10);
 
     // 상대 클라이언트 대기 및 게임 시작 메세지 수신
-    let mut buf = [0; 1];
-    loop {
-        let nbytes = socket.read_exact(&mut buf).await;
-        match nbytes {
-            Ok(_) => {
-                let msg = Message::from(buf[0]);
+    let (players, teams, you, token, grace, rating) = loop {
+        match read_message(&mut socket).await {
+            Ok(msg) => {
                 if DEBUG {
                     println!("[Client] GOT message from server: {:?}", msg);
                 }
@@ -83,77 +343,192 @@ async fn main() {
                     Message::Waiting => {
                         continue;
                     }
-                    Message::GameStart => {
-                        break;
+                    // 친구에게 알려줄 참가 코드
+                    Message::RoomCreated { code } => {
+                        println!("[Client] Room code: {} (waiting for players...)", code);
+                    }
+                    // 방이 없거나 비밀번호가 틀린 경우
+                    Message::Reject { reason } => {
+                        println!("[Client] Server rejected lobby request: {}", reason);
+                        return;
+                    }
+                    Message::GameStart {
+                        players,
+                        teams,
+                        you,
+                        token,
+                        grace,
+                        rating,
+                    } => {
+                        break (players, teams, you, token, grace, rating);
                     }
                     _ => {} // 위 메세지 타입 외에는 무시
                 }
             }
-            Err(_) => {
+            Err(e) => {
                 if DEBUG {
-                    println!("[Client] Server disconnected before game start");
+                    println!("[Client] Server disconnected before game start: {}", e);
                 }
                 return;
             }
         }
-    }
+    };
 
     // GameManager 쓰레드
+    let heartbeat = (opts.ping_interval, opts.timeout);
+    let session = Session {
+        addr,
+        name,
+        token,
+        grace: Duration::try_from_secs_f32(grace).unwrap_or_default(),
+    };
     let mgr_handle = tokio::spawn(async move {
-        spawn_manager(socket, mgr_writer, game_reader).await;
+        spawn_manager(socket, heartbeat, session, mgr_writer, game_reader).await;
     });
 
     // Game 쓰레드
+    let mini_board = opts.mini_board;
     let game_handle = tokio::spawn(async move {
-        spawn_game(game_writer, mgr_reader).await;
+        spawn_game(
+            game_writer,
+            mgr_reader,
+            vocab_generator,
+            mode,
+            (players, teams, you),
+            rating,
+            mini_board,
+        )
+        .await;
     });
 
     // 쓰레드 종료 대기
-    mgr_handle.await.unwrap();
-    game_handle.await.unwrap();
+    if let Err(e) = mgr_handle.await {
+        println!("[Client] GameManager failed: {}", e);
+    }
+    if let Err(e) = game_handle.await {
+        println!("[Client] Game failed: {}", e);
+    }
 
     if DEBUG {
         println!("[Client] main exited");
     }
 }
 
+fn default_name() -> String {
+    std::env::var("USER").unwrap_or_else(|_| "player".to_string())
+}
+
 // GameManager 쓰레드
 async fn spawn_manager(
-    mut socket: TcpStream,
+    socket: TcpStream,
+    heartbeat: (Duration, Duration),
+    session: Session,
     mgr_writer: Sender<Message>,
     mut game_reader: Receiver<Message>,
 ) {
-    let mut buf = [0; 1];
+    let (reader, mut writer) = socket.into_split();
+    let mut messages = spawn_message_reader(reader);
+
+    let (ping_interval, server_timeout) = heartbeat;
+    let mut ping = interval(ping_interval);
+    ping.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    let mut last_seen = Instant::now();
+
+    // 서버 연결이 끊겼는지, 끊겨서 보내지 못한 게임 메세지
+    let mut lost = false;
+    let mut unsent: Option<Message> = None;
 
     // I/O Multiplexing
     loop {
+        // 서버 연결이 끊기면 세션 토큰으로 다시 접속한다
+        if lost {
+            lost = false;
+            let socket = match reconnect(&session).await {
+                Some(socket) => socket,
+                None => {
+                    if DEBUG {
+                        println!("[GameManager] Failed to reconnect");
+                    }
+                    break;
+                }
+            };
+            let (reader, new_writer) = socket.into_split();
+            messages = spawn_message_reader(reader);
+            writer = new_writer;
+            last_seen = Instant::now();
+            if DEBUG {
+                println!("[GameManager] Reconnected");
+            }
+
+            if let Some(msg) = unsent.take() {
+                if write_message(&mut writer, &msg).await.is_err() {
+                    unsent = Some(msg);
+                    lost = true;
+                    continue;
+                }
+            }
+        }
+
         tokio::select! {
 
             // 서버 메세지를 게임에게 전달 상대 클라이언트에게 전달
             // MyClientHandler -(TCP)> GameManager -(channel)> Game
-            nbytes = socket.read_exact(&mut buf) => {
-                match nbytes {
-                    Ok(_) => {
-                        let srv_msg = Message::from(buf[0]);
+            srv_msg = messages.recv() => {
+                match srv_msg {
+                    Some(Ok(srv_msg)) => {
+                        last_seen = Instant::now();
                         if DEBUG { println!("[GameManager] GOT message from server: {:?}", srv_msg); }
                         match srv_msg {
-                            Message::Attacked => {
-                                mgr_writer.send(srv_msg).await.unwrap();   // 게임에게 서버 메세지 전달
+                            Message::Ping { sent_at } => {
+                                let result = write_message(&mut writer, &Message::Pong { sent_at }).await;
+                                if result.is_err() {
+                                    lost = true;  // 서버 연결 끊김
+                                }
+                            }
+                            // 지연 시간 표시를 위해 Pong도 게임에게 전달
+                            Message::Attacked { .. } | Message::Pong { .. } | Message::PlayerState { .. } | Message::PlayerBoard { .. } | Message::PlayerOut { .. } | Message::PlayerAway { .. } | Message::PlayerBack { .. } => {
+                                // 게임에게 서버 메세지 전달
+                                let result = mgr_writer.send(srv_msg).await;
+                                if result.is_err() {
+                                    break;  // 게임 종료됨
+                                }
                             }
-                            Message::GameOver => {
-                                mgr_writer.send(srv_msg).await.unwrap();   // 게임에게 서버 메세지 전달
+                            Message::MatchOver { .. } => {
+                                _ = mgr_writer.send(srv_msg).await;   // 게임에게 서버 메세지 전달
                                 break;
                             }
                             _ => {} // 위 메세지 외에는 무시
                         }
                     }
-                    Err(_) => {
+                    // 읽지 못한 메세지 하나는 건너뛴다
+                    Some(Err(e)) if e.is_recoverable() => {
+                        if DEBUG { println!("[GameManager] Skipped message: {}", e); }
+                    }
+                    Some(Err(e)) => {
+                        if DEBUG { println!("[GameManager] Server error: {}", e); }
+                        lost = true;
+                    }
+                    None => {
                         if DEBUG { println!("[GameManager] Server disconnected"); }
-                        break;
+                        lost = true;
                     }
                 }
             }
 
+            // 주기적으로 Ping을 보내고, 서버가 응답하지 않으면 다시 접속한다
+            _ = ping.tick() => {
+                if last_seen.elapsed() > server_timeout {
+                    if DEBUG { println!("[GameManager] Server timed out"); }
+                    lost = true;
+                    continue;
+                }
+                let sent_at = chrono::Utc::now().timestamp_millis();
+                if write_message(&mut writer, &Message::Ping { sent_at }).await.is_err() {
+                    if DEBUG { println!("[GameManager] Server disconnected"); }
+                    lost = true;
+                }
+            }
+
             // 게임 메세지를 서버에게 전달
             // Game -(channel)> GameManager -(TCP)> MyClientHandler
             msg = game_reader.recv() => {
@@ -161,12 +536,15 @@ async fn spawn_manager(
                     Some(msg) => {
                         if DEBUG { println!("[GameManager] GOT message from game: {:?}", msg); }
                         match msg {
-                            Message::Attacked => {
-                                socket.write_all(&[msg as u8]).await.unwrap();
-                            }
-                            Message::GameOver => {
-                                socket.write_all(&[msg as u8]).await.unwrap();
-                                break;  // GameManager 종료
+                            // 내 게임이 끝나도 매치가 끝날 때까지(MatchOver) 연결을 유지한다
+                            Message::Attacked { .. } | Message::Target { .. } | Message::State { .. } | Message::Board { .. } | Message::GameOver { .. } | Message::Victory { .. } => {
+                                let result = write_message(&mut writer, &msg).await;
+                                if result.is_err() {
+                                    if DEBUG { println!("[GameManager] Server disconnected"); }
+                                    // 다시 접속하면 보낸다
+                                    unsent = Some(msg);
+                                    lost = true;
+                                }
                             }
                             _ => {} // 위 메세지 외에는 무시
                         }
@@ -182,140 +560,396 @@ async fn spawn_manager(
 
     // Tear down
     game_reader.close();
-    while let Some(_) = game_reader.recv().await {}
+    while game_reader.recv().await.is_some() {}
 
-    socket.shutdown().await.unwrap();
+    _ = writer.shutdown().await;
     if DEBUG {
         println!("[GameManager] Closed");
     }
 }
 
+// 재접속에 필요한 정보
+struct Session {
+    addr: String,
+    name: String,
+    token: String,
+    grace: Duration,
+}
+
+// 서버가 자리를 잡아 두는 유예 시간 동안 재접속을 시도한다
+async fn reconnect(session: &Session) -> Option<TcpStream> {
+    let deadline = Instant::now() + session.grace;
+    while Instant::now() < deadline {
+        let attempt = async {
+            let mut socket = TcpStream::connect(&session.addr).await.ok()?;
+            let hello = Message::resume(&session.name, &session.token);
+            write_message(&mut socket, &hello).await.ok()?;
+            match read_message(&mut socket).await.ok()? {
+                Message::Welcome { .. } => match read_message(&mut socket).await.ok()? {
+                    Message::Resumed => Some(Ok(socket)),
+                    Message::Reject { reason } => Some(Err(reason)),
+                    _ => None,
+                },
+                Message::Reject { reason } => Some(Err(reason)),
+                _ => None,
+            }
+        };
+        // ncurses의 timeout과 이름이 겹치므로 전체 경로로 쓴다
+        match tokio::time::timeout(deadline - Instant::now(), attempt).await {
+            Ok(Some(Ok(socket))) => return Some(socket),
+            // 세션이 끝났으면 더 시도하지 않는다
+            Ok(Some(Err(reason))) => {
+                if DEBUG {
+                    println!("[GameManager] Reconnect rejected: {}", reason);
+                }
+                return None;
+            }
+            Ok(None) => sleep(RECONNECT_INTERVAL).await,
+            Err(_) => return None,
+        }
+    }
+    None
+}
+
+// 사용자별 데이터 디렉토리의 vocab 캐시 (<data_dir>/raingame/vocab/<hash>.txt)
+fn cached_vocab_path(digest: &str) -> Option<PathBuf> {
+    // 서버가 보낸 해시를 파일 이름으로 쓰므로 16진수만 허용
+    if digest.is_empty() || !digest.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    dirs::data_dir().map(|dir| {
+        dir.join("raingame")
+            .join("vocab")
+            .join(format!("{}.txt", digest))
+    })
+}
+
+fn read_cached_vocab(digest: &str) -> Option<String> {
+    let contents = fs::read_to_string(cached_vocab_path(digest)?).ok()?;
+    // 캐시 파일이 깨졌으면 다시 받는다
+    if vocab_hash(contents.as_bytes()) == digest {
+        Some(contents)
+    } else {
+        None
+    }
+}
+
+fn write_cached_vocab(digest: &str, contents: &str) {
+    if let Some(path) = cached_vocab_path(digest) {
+        if let Some(dir) = path.parent() {
+            _ = fs::create_dir_all(dir);
+        }
+        _ = fs::write(path, contents);
+    }
+}
+
 fn write_game_result(result_string: &str) {
     let now = Utc::now();
     let filename = format!("./log/{}.log", now.format("%Y-%m-%d_%H-%M-%S"));
 
     let path = Path::new("log");
-    if !path.exists() {
-        fs::create_dir(path).expect("Failed to create log directory");
+    if !path.exists() && fs::create_dir(path).is_err() {
+        return;
     }
 
-    let mut file = File::create(&filename).unwrap();
-    file.write_all(result_string.as_bytes()).unwrap();
+    if let Ok(mut file) = File::create(&filename) {
+        _ = file.write_all(result_string.as_bytes());
+    }
+}
+
+// 매치의 다른 플레이어 상태
+struct PlayerStatus {
+    name: String,
+    team: usize,
+    score: i32,
+    life: i32,
+    combo: i32,
+    // 받을 공격 세기
+    pending: u32,
+    // 탈락 사유
+    out: Option<String>,
+    // 재접속 유예 시간이 끝나는 시각
+    away: Option<Instant>,
+}
+
+// 다른 플레이어의 상태와 화면을 갱신한다 (게임 중과 관전 중에 같이 쓴다)
+// 플레이어 상태 메세지가 아니면 그대로 돌려주고, 없는 플레이어 번호는 무시한다
+fn apply_player_update(
+    players: &mut [PlayerStatus],
+    boards: &mut [Option<Board>],
+    msg: Message,
+) -> Option<Message> {
+    match msg {
+        Message::PlayerState {
+            player,
+            score,
+            life,
+            combo,
+            pending,
+        } => {
+            if let Some(status) = players.get_mut(player) {
+                status.score = score;
+                status.life = life;
+                status.combo = combo;
+                status.pending = pending;
+            }
+        }
+        Message::PlayerBoard { player, board } => {
+            if let (Some(status), Some(slot)) = (players.get_mut(player), boards.get_mut(player)) {
+                status.score = board.score;
+                status.life = board.life;
+                *slot = Some(board);
+            }
+        }
+        Message::PlayerOut {
+            player,
+            score,
+            reason,
+        } => {
+            if let Some(status) = players.get_mut(player) {
+                status.score = score;
+                status.away = None;
+                status.out = Some(reason);
+            }
+        }
+        Message::PlayerAway { player, grace } => {
+            if let Some(status) = players.get_mut(player) {
+                status.away =
+                    Some(Instant::now() + Duration::try_from_secs_f32(grace).unwrap_or_default());
+            }
+        }
+        Message::PlayerBack { player } => {
+            if let Some(status) = players.get_mut(player) {
+                status.away = None;
+            }
+        }
+        msg => return Some(msg),
+    }
+    None
 }
 
 // 게임 쓰레드
-async fn spawn_game(game_writer: Sender<Message>, mut mgr_reader: Receiver<Message>) {
-    initscr();
-    cbreak();
-    noecho();
-    timeout(0);
-    curs_set(CURSOR_VISIBILITY::CURSOR_INVISIBLE);
-    keypad(stdscr(), true);
+async fn spawn_game(
+    game_writer: Sender<Message>,
+    mut mgr_reader: Receiver<Message>,
+    vocab_generator: VocabGenerator,
+    mode: GameMode,
+    (players, teams, you): (Vec<String>, Vec<usize>, usize),
+    rating: Rating,
+    mini_board: bool,
+) {
+    init_screen();
 
-    // Color 세팅
-    start_color();
-    use_default_colors();
-    ncurses::init_pair(WordColor::Red as i16, ncurses::COLOR_RED, -1);
-    ncurses::init_pair(WordColor::Green as i16, ncurses::COLOR_GREEN, -1);
-    ncurses::init_pair(WordColor::Yellow as i16, ncurses::COLOR_YELLOW, -1);
+    let mut game = Game::with_source(HEIGHT, WIDTH, Box::new(vocab_generator));
+    game.set_mode(mode);
+
+    let mut players = players
+        .into_iter()
+        .zip(teams)
+        .map(|(name, team)| PlayerStatus {
+            name,
+            team,
+            score: 0,
+            life: game.get_life(),
+            combo: 0,
+            pending: 0,
+            out: None,
+            away: None,
+        })
+        .collect::<Vec<PlayerStatus>>();
+    let mut boards: Vec<Option<Board>> = vec![None; players.len()];
+    let my_team = players[you].team;
+    let names_of = |teammates: bool| {
+        players
+            .iter()
+            .enumerate()
+            .filter(|(index, player)| *index != you && (player.team == my_team) == teammates)
+            .map(|(_, player)| player.name.clone())
+            .collect::<Vec<String>>()
+    };
+    let opponents = names_of(false);
+    let teammates = names_of(true);
+
+    // 시작 전에 상대와 내 레이팅, 결과에 따른 변화량을 보여준다
+    erase();
+    addstr(&format!(
+        "VS {} (rating {})\n",
+        opponents.join(", "),
+        rating.opponent
+    ));
+    if !teammates.is_empty() {
+        addstr(&format!("WITH {}\n", teammates.join(", ")));
+    }
+    addstr(&format!(
+        "Your rating: {} (win {:+} / lose {:+})\n",
+        rating.before, rating.on_win, rating.on_loss
+    ));
+    if opponents.len() > 1 {
+        addstr("Press TAB to change your attack target\n");
+    }
+    refresh();
+    napms(2000);
+
+    // 미니 보드를 쓸 때만 서버가 상대 화면을 보내 준다
+    if mini_board {
+        send_to_manager(&game_writer, Message::MiniBoard { enabled: true }).await;
+    }
 
-    let mut game = Game::new(HEIGHT, WIDTH);
     let line = "-".repeat(WIDTH as usize);
-    let mut game_state = GameState::StartGame;
+    let mut latency = None;
+    let mut target = Target::Random;
+    let mut sent_state = (game.get_score(), game.get_life(), game.get_combo(), 0);
+    // 관전자와 다른 플레이어용 화면은 두 프레임마다, 상태는 바뀌지 않아도 주기적으로 보낸다
+    let mut frame = 0;
+    // 내 게임이 끝나고 매치 결과를 기다리는 중 (탈락하면 관전)
+    let mut finished = false;
+    let mut winner = None;
+    let mut match_over = false;
+    // 마지막으로 탈락한 상대와 사유
+    let mut last_out = None;
 
-    loop {
-        // GameManager로부터 메세지 non-blocking으로 받기
-        match mgr_reader.try_recv() {
-            Ok(msg) => {
-                if DEBUG {
-                    println!("[Game] GOT message from manager: {:?}", msg);
-                }
-                match msg {
-                    Message::GameOver => {
-                        game.set_game_state(GameState::Win);
-                        break;
+    'game: loop {
+        // GameManager로부터 메세지 non-blocking으로 받기 (밀리지 않도록 쌓인 메세지를 모두 처리한다)
+        loop {
+            match mgr_reader.try_recv() {
+                Ok(msg) => {
+                    if DEBUG {
+                        println!("[Game] GOT message from manager: {:?}", msg);
                     }
-                    Message::Attacked => {
-                        game.spawn_word(WordColor::Red);
+                    if let Message::PlayerOut { player, reason, .. } = &msg {
+                        if let Some(status) = players.get(*player) {
+                            last_out = Some(format!("{} {}", status.name, reason));
+                        }
+                    }
+                    match apply_player_update(&mut players, &mut boards, msg) {
+                        Some(Message::MatchOver { winner: result }) => {
+                            winner = result;
+                            match_over = true;
+                            if winner == Some(my_team) {
+                                game.set_game_state(GameState::Win);
+                            } else {
+                                game.set_game_state(GameState::Lose);
+                            }
+                            break 'game;
+                        }
+                        // 탈락한 뒤에 받은 공격은 무시
+                        Some(Message::Attacked { kind, strength, .. }) if !finished => {
+                            game.receive_attack(kind, strength);
+                        }
+                        Some(Message::Pong { sent_at }) => {
+                            latency = Some(chrono::Utc::now().timestamp_millis() - sent_at);
+                        }
+                        _ => {} // 위 메세지 타입 외에는 무시
                     }
-                    _ => {} // 위 메세지 타입 외에는 무시
                 }
-            }
-            Err(TryRecvError::Empty) => {} // 읽을 메세지 없음
-            Err(TryRecvError::Disconnected) => {
-                if DEBUG {
-                    println!("[Game] Manager channel closed");
+                Err(TryRecvError::Empty) => break, // 읽을 메세지 없음
+                Err(TryRecvError::Disconnected) => {
+                    if DEBUG {
+                        println!("[Game] Manager channel closed");
+                    }
+                    break 'game;
                 }
-                break;
             }
         }
 
         erase();
 
         let input = getch();
-        let input_char = char::from_u32(input as u32);
-        match (input, input_char) {
-            (KEY_BACKSPACE, _)
-            | (KEY_DC, _)
-            | (127, _)
-            | (_, Some('\u{0008}'))
-            | (_, Some('='))
-            | (_, Some('\x7f')) => {
-                game.pop_input_string();
-            }
-            (KEY_ENTER, _) | (KEY_SEND, _) | (_, Some('\n')) => {
-                game_state = game.enter_input_string();
+
+        // Tab으로 공격 대상 변경
+        if input == '\t' as i32 && !finished {
+            target = next_target(target, you, &players);
+            send_to_manager(&game_writer, Message::Target { target }).await;
+        }
+
+        // 탈락한 뒤에는 매치가 끝날 때까지 다른 플레이어의 상태를 본다
+        if finished {
+            addstr(&format!("Score: {}\n", game.get_score()));
+            if game.get_game_state() == GameState::Lose {
+                addstr("YOU ARE OUT! Watching the rest of the match...\n\n");
+            } else {
+                addstr("Waiting for the match result...\n\n");
             }
-            (_, Some(c)) => {
-                game.push_input_string(c);
+            for (index, player) in players.iter().enumerate() {
+                if index != you {
+                    addstr(&format!("{}\n", player_status_string(player)));
+                }
             }
-            _ => {}
+            refresh();
+            yield_now().await;
+            napms(100);
+            continue;
         }
 
+        let attack_word = game.get_attack_string();
+        let game_state = game.handle_input(input);
+
         if game_state == GameState::CompleteAttackWord {
-            let result = game_writer.send(Message::Attacked).await;
-            yield_now().await;
-            match result {
-                Ok(()) => {
-                    if DEBUG {
-                        println!("[Game] Sent message to manager: {:?}", Message::Attacked);
-                    }
-                }
-                Err(e) => {
-                    if DEBUG {
-                        println!("[Game] Failed to send message to manager: {:?}", e);
-                    }
-                }
-            }
+            let (kind, strength) = game.get_last_attack();
+            let msg = Message::Attacked {
+                word: attack_word,
+                kind,
+                strength,
+            };
+            send_to_manager(&game_writer, msg).await;
         }
 
-        game_state = game.update(); // game_state = InProgress or Lose
+        let game_state = game.update(); // game_state = InProgress, Lose or Win
 
-        if game_state == GameState::Lose {
-            let result = game_writer.send(Message::GameOver).await;
-            yield_now().await;
-            match result {
-                Ok(()) => {
-                    if DEBUG {
-                        println!("[Game] Sent message to manager: {:?}", Message::GameOver);
-                    }
-                }
-                Err(e) => {
-                    if DEBUG {
-                        println!("[Game] Failed to send message to manager: {:?}", e);
-                    }
-                }
-            }
-            break;
+        if matches!(game_state, GameState::Lose | GameState::Win) {
+            // 패배하면 GameOver, 게임 모드의 승리 조건을 달성하면 Victory
+            let score = game.get_score();
+            let msg = if game_state == GameState::Lose {
+                Message::GameOver { score }
+            } else {
+                Message::Victory { score }
+            };
+            send_to_manager(&game_writer, msg).await;
+            finished = true;
+            continue;
         };
 
+        // 상태가 바뀌면 서버에 알린다 (다른 플레이어 화면과 LOWEST 공격 대상에 쓰인다)
+        let state = (
+            game.get_score(),
+            game.get_life(),
+            game.get_combo(),
+            game.get_pending_attack().0,
+        );
+        frame += 1;
+        if state != sent_state || frame % STATE_FRAMES == 0 {
+            sent_state = state;
+            let (score, life, combo, pending) = state;
+            let msg = Message::State {
+                score,
+                life,
+                combo,
+                pending,
+            };
+            send_to_manager(&game_writer, msg).await;
+        }
+        if frame % BOARD_FRAMES == 0 {
+            let board = game.get_board();
+            send_to_manager(&game_writer, Message::Board { board }).await;
+        }
+
         addstr(&format!("Score: {}\n", game.get_score()));
         game.draw_words();
 
         // Print input prompt
-        let input_prompt = format!("> {}", game.get_input_string());
+        // Blind 디버프 동안 입력 줄을 가린다
+        let input_prompt = if game.has_debuff(AttackKind::Blind) {
+            "> ".to_string()
+        } else {
+            format!("> {}", game.get_input_string())
+        };
         let life_string = format!("LIFE: {}", game.get_life());
-        let attack_string = format!("ATTACK: {}", game.get_attack_string());
+        let attack_string = format!(
+            "ATTACK({}): {}",
+            game.get_attack_kind().get_name(),
+            game.get_attack_string()
+        );
+        let combo_string = format!("COMBO: {}", game.get_combo());
 
         attron(COLOR_PAIR(WordColor::Green as i16));
         mvprintw(0, WIDTH - life_string.len() as i32, &life_string);
@@ -325,25 +959,143 @@ async fn spawn_game(game_writer: Sender<Message>, mut mgr_reader: Receiver<Messa
         mvprintw(1, WIDTH - attack_string.len() as i32, &attack_string);
         attroff(COLOR_PAIR(1));
 
+        mvprintw(2, WIDTH - combo_string.len() as i32, &combo_string);
+
+        // 받을 공격 경고 (세기만큼 #, 다음 공격까지 남은 시간)
+        if let (pending, Some(next)) = game.get_pending_attack() {
+            let warning_string = format!(
+                "INCOMING: {} {}s",
+                "#".repeat(pending as usize),
+                next.as_secs() + 1
+            );
+            attron(COLOR_PAIR(WordColor::Red as i16));
+            mvprintw(1, 0, &warning_string);
+            attroff(COLOR_PAIR(WordColor::Red as i16));
+        }
+
+        // 걸려 있는 디버프와 남은 시간 (초)
+        let debuffs = game.get_debuffs();
+        if !debuffs.is_empty() {
+            let debuff_string = debuffs
+                .iter()
+                .map(|(kind, left)| format!("{} {}s", kind.get_name(), left.as_secs() + 1))
+                .collect::<Vec<String>>()
+                .join(" ");
+            attron(COLOR_PAIR(WordColor::Yellow as i16));
+            mvprintw(3, WIDTH - debuff_string.len() as i32, &debuff_string);
+            attroff(COLOR_PAIR(WordColor::Yellow as i16));
+        }
+
+        // 구분선 위에 공격 대상과 상대 점수 (> 표시가 고른 상대)
+        let standings_string = format!(
+            " TARGET({}) {} ",
+            target.get_name(),
+            players
+                .iter()
+                .enumerate()
+                .filter(|(_, player)| player.team != my_team)
+                .map(|(index, player)| {
+                    let marker = if target == Target::Player(index) {
+                        ">"
+                    } else {
+                        ""
+                    };
+                    format!("{}{}", marker, player_status_string(player))
+                })
+                .collect::<Vec<String>>()
+                .join(" | ")
+        );
         attron(COLOR_PAIR(WordColor::Yellow as i16));
         mvprintw(HEIGHT - 2, 0, &line);
+        mvprintw(HEIGHT - 2, 1, &standings_string);
         attroff(COLOR_PAIR(3));
 
+        // 팀전이면 구분선 오른쪽에 팀원의 점수와 목숨
+        if !teammates.is_empty() {
+            let team_string = format!(
+                " TEAM {} ",
+                players
+                    .iter()
+                    .enumerate()
+                    .filter(|(index, player)| *index != you && player.team == my_team)
+                    .map(|(_, player)| match (&player.out, player.away) {
+                        (None, None) =>
+                            format!("{} LIFE:{}", player_status_string(player), player.life),
+                        _ => player_status_string(player),
+                    })
+                    .collect::<Vec<String>>()
+                    .join(" | ")
+            );
+            attron(COLOR_PAIR(WordColor::Green as i16));
+            mvprintw(
+                HEIGHT - 2,
+                WIDTH - 1 - team_string.len() as i32,
+                &team_string,
+            );
+            attroff(COLOR_PAIR(WordColor::Green as i16));
+        }
+
+        // 터미널이 충분히 넓으면 오른쪽에 상대 패널
+        if COLS() > WIDTH + PANEL_WIDTH {
+            draw_opponent_panel(&players, &boards, you, target, mini_board);
+        }
+
         mvprintw(HEIGHT - 1, 0, input_prompt.as_str());
+        if let Some(latency) = latency {
+            let latency_string = format!("PING: {}ms", latency);
+            mvprintw(
+                HEIGHT - 1,
+                WIDTH - latency_string.len() as i32,
+                &latency_string,
+            );
+        }
         refresh();
         yield_now().await;
         napms(100);
     }
 
-    let game_result = match game.get_game_state() {
-        GameState::Lose => "YOU LOSE!\n",
-        GameState::Win => "YOU WIN!\n",
-        _ => "Server disconnected!\n",
+    let game_result = match (match_over, game.get_game_state(), &last_out) {
+        // 마지막 상대가 접속이 끊겨 탈락한 경우 사유를 함께 보여준다
+        (true, GameState::Win, Some(out)) if !out.ends_with("game over") => {
+            format!("YOU WIN! ({})\n", out)
+        }
+        (true, GameState::Win, _) => "YOU WIN!\n".to_string(),
+        (true, _, _) => match winner {
+            Some(winner) => {
+                let winners = players
+                    .iter()
+                    .filter(|player| player.team == winner)
+                    .map(|player| player.name.clone())
+                    .collect::<Vec<String>>();
+                format!("YOU LOSE! {} wins\n", winners.join(", "))
+            }
+            None => "YOU LOSE!\n".to_string(),
+        },
+        _ => "Server disconnected!\n".to_string(),
+    };
+    // 매치가 끝난 경우 서버도 같은 변화량을 반영한다 (승자가 없으면 그대로)
+    let rating_change = match (match_over, winner) {
+        (true, Some(winner)) if winner == my_team => Some(rating.on_win),
+        (true, Some(_)) => Some(rating.on_loss),
+        _ => None,
     };
 
     erase();
-    addstr(game_result);
+    addstr(&game_result);
     addstr(&format!("Final Score: {}\n", game.get_score()));
+    for (index, player) in players.iter().enumerate() {
+        if index != you {
+            addstr(&format!("{}'s Score: {}\n", player.name, player.score));
+        }
+    }
+    if let Some(change) = rating_change {
+        addstr(&format!(
+            "Rating: {} -> {} ({:+})\n",
+            rating.before,
+            rating.before + change,
+            change
+        ));
+    }
     refresh();
 
     napms(1000);
@@ -362,11 +1114,320 @@ async fn spawn_game(game_writer: Sender<Message>, mut mgr_reader: Receiver<Messa
     // Teardown
     let result_string = format!("{} with a score of {}", game_result, game.get_score());
     write_game_result(&result_string);
-    
+
     mgr_reader.close();
-    while let Some(_) = mgr_reader.recv().await {}
+    while mgr_reader.recv().await.is_some() {}
 
     if DEBUG {
         println!("[Game] Closed");
     }
 }
+
+// ncurses 화면과 색 초기화
+fn init_screen() {
+    setlocale(LcCategory::all, "");
+    initscr();
+    cbreak();
+    noecho();
+    timeout(0);
+    curs_set(CURSOR_VISIBILITY::CURSOR_INVISIBLE);
+    keypad(stdscr(), true);
+
+    // Color 세팅
+    start_color();
+    use_default_colors();
+    ncurses::init_pair(WordColor::Red as i16, ncurses::COLOR_RED, -1);
+    ncurses::init_pair(WordColor::Green as i16, ncurses::COLOR_GREEN, -1);
+    ncurses::init_pair(WordColor::Yellow as i16, ncurses::COLOR_YELLOW, -1);
+}
+
+// 관전 쓰레드
+// 플레이어들의 화면을 나란히 그리고, 매치가 끝나면 결과를 보여준다
+async fn spawn_spectator(socket: TcpStream, players: Vec<String>, teams: Vec<usize>) {
+    let (reader, mut writer) = socket.into_split();
+    let mut messages = spawn_message_reader(reader);
+
+    init_screen();
+
+    let mut players = players
+        .into_iter()
+        .zip(teams)
+        .map(|(name, team)| PlayerStatus {
+            name,
+            team,
+            score: 0,
+            life: 0,
+            combo: 0,
+            pending: 0,
+            out: None,
+            away: None,
+        })
+        .collect::<Vec<PlayerStatus>>();
+    let mut boards: Vec<Option<Board>> = vec![None; players.len()];
+    let mut winner = None;
+    let mut match_over = false;
+
+    // 화면 너비를 플레이어 수만큼 나눈다 (패널 사이 구분선 한 칸)
+    let panel_width = (COLS().max(WIDTH) / players.len() as i32).max(2);
+    let line = "-".repeat(panel_width as usize - 1);
+
+    'watch: loop {
+        // 서버 메세지 non-blocking으로 받기
+        loop {
+            match messages.try_recv() {
+                Ok(Ok(msg)) => {
+                    // 플레이어 상태 외에는 매치 종료만 본다
+                    if let Some(Message::MatchOver { winner: result }) =
+                        apply_player_update(&mut players, &mut boards, msg)
+                    {
+                        winner = result;
+                        match_over = true;
+                        break 'watch;
+                    }
+                }
+                Err(TryRecvError::Empty) => break, // 읽을 메세지 없음
+                Ok(Err(e)) if e.is_recoverable() => {} // 읽지 못한 메세지 하나는 건너뛴다
+                Ok(Err(_)) | Err(TryRecvError::Disconnected) => {
+                    if DEBUG {
+                        println!("[Spectator] Server disconnected");
+                    }
+                    break 'watch;
+                }
+            }
+        }
+
+        // q로 관전 종료
+        if getch() == 'q' as i32 {
+            break;
+        }
+
+        erase();
+        for (index, player) in players.iter().enumerate() {
+            let left = index as i32 * panel_width;
+            let width = panel_width - 1;
+            let clip = |text: &str, from: i32| {
+                text.chars()
+                    .take((width - from).max(0) as usize)
+                    .collect::<String>()
+            };
+
+            // 이름과 점수, 목숨
+            let header = match (&player.out, player.away) {
+                (None, None) => format!("{} LIFE:{}", player_status_string(player), player.life),
+                _ => player_status_string(player),
+            };
+            mvprintw(0, left, &clip(&header, 0));
+
+            // 떨어지는 단어 (가로 위치는 패널 너비에 맞춘다)
+            if let Some(board) = &boards[index] {
+                for word in &board.words {
+                    if word.y < 1 || word.y >= HEIGHT - 2 {
+                        continue;
+                    }
+                    let x = word.x * width / WIDTH;
+                    let color = word.color as i16;
+                    attron(COLOR_PAIR(color));
+                    mvprintw(word.y, left + x, &clip(&word.text, x));
+                    attroff(COLOR_PAIR(color));
+                }
+                mvprintw(HEIGHT - 1, left, &clip(&format!("> {}", board.input), 0));
+            }
+
+            attron(COLOR_PAIR(WordColor::Yellow as i16));
+            mvprintw(HEIGHT - 2, left, &line);
+            attroff(COLOR_PAIR(WordColor::Yellow as i16));
+            if index + 1 < players.len() {
+                for y in 0..HEIGHT {
+                    mvprintw(y, left + width, "|");
+                }
+            }
+        }
+        mvprintw(HEIGHT, 0, "WATCHING (press q to quit)");
+        refresh();
+        yield_now().await;
+        napms(100);
+    }
+
+    let game_result = match (match_over, winner) {
+        (true, Some(winner)) => {
+            let winners = players
+                .iter()
+                .filter(|player| player.team == winner)
+                .map(|player| player.name.clone())
+                .collect::<Vec<String>>();
+            format!("{} wins\n", winners.join(", "))
+        }
+        (true, None) => "No winner\n".to_string(),
+        (false, _) => "Stopped watching\n".to_string(),
+    };
+
+    erase();
+    addstr(&game_result);
+    for player in &players {
+        addstr(&format!("{}'s Score: {}\n", player.name, player.score));
+    }
+    addstr("Press any key to exit...");
+    refresh();
+
+    loop {
+        let input = getch();
+        if input > -1 {
+            break;
+        }
+        napms(100);
+    }
+    endwin();
+
+    _ = writer.shutdown().await;
+    if DEBUG {
+        println!("[Spectator] Closed");
+    }
+}
+
+// 게임 메세지를 GameManager에게 전달
+async fn send_to_manager(game_writer: &Sender<Message>, msg: Message) {
+    let result = game_writer.send(msg.clone()).await;
+    yield_now().await;
+    match result {
+        Ok(()) => {
+            if DEBUG {
+                println!("[Game] Sent message to manager: {:?}", msg);
+            }
+        }
+        Err(e) => {
+            if DEBUG {
+                println!("[Game] Failed to send message to manager: {:?}", e);
+            }
+        }
+    }
+}
+
+// Tab을 누를 때마다 RANDOM -> LOWEST -> 남아 있는 상대 순서로 공격 대상을 바꾼다 (팀원 제외)
+fn next_target(target: Target, you: usize, players: &[PlayerStatus]) -> Target {
+    let mut alive = (0..players.len())
+        .filter(|&index| players[index].team != players[you].team && players[index].out.is_none());
+    match target {
+        Target::Random => Target::LowestScore,
+        Target::LowestScore => alive.next().map(Target::Player).unwrap_or(Target::Random),
+        Target::Player(current) => alive
+            .find(|&index| index > current)
+            .map(Target::Player)
+            .unwrap_or(Target::Random),
+    }
+}
+
+// 상대마다 점수, 목숨, 콤보, 받을 공격을 보여주고, 원하면 공격 대상의 화면을 작게 그린다
+fn draw_opponent_panel(
+    players: &[PlayerStatus],
+    boards: &[Option<Board>],
+    you: usize,
+    target: Target,
+    mini_board: bool,
+) {
+    let left = WIDTH + 1;
+    for y in 0..HEIGHT {
+        mvprintw(y, WIDTH, "|");
+    }
+    let clip = |text: &str, from: i32| {
+        text.chars()
+            .take((PANEL_WIDTH - from).max(0) as usize)
+            .collect::<String>()
+    };
+
+    // 미니 보드는 패널 아래쪽 절반 (제목 한 줄 + 단어 + 입력 줄)
+    let mini_height = HEIGHT / 2;
+    let mini_top = HEIGHT - mini_height - 1;
+    let bottom = if mini_board { mini_top } else { HEIGHT };
+
+    attron(COLOR_PAIR(WordColor::Yellow as i16));
+    mvprintw(0, left, "OPPONENTS");
+    attroff(COLOR_PAIR(WordColor::Yellow as i16));
+    let opponents = (0..players.len())
+        .filter(|&index| players[index].team != players[you].team)
+        .collect::<Vec<usize>>();
+    let mut y = 1;
+    for &index in &opponents {
+        if y + 1 >= bottom {
+            break;
+        }
+        let player = &players[index];
+        let marker = if target == Target::Player(index) {
+            ">"
+        } else {
+            " "
+        };
+        mvprintw(
+            y,
+            left,
+            &clip(&format!("{}{}", marker, player_status_string(player)), 0),
+        );
+        if player.out.is_none() {
+            let stats = format!("  LIFE:{} COMBO:{} ", player.life, player.combo);
+            mvprintw(y + 1, left, &clip(&stats, 0));
+            if player.pending > 0 {
+                let from = stats.len() as i32;
+                // 패널 너비를 넘는 만큼은 만들지 않는다
+                let count = (player.pending as usize).min((PANEL_WIDTH - from).max(0) as usize);
+                attron(COLOR_PAIR(WordColor::Red as i16));
+                mvprintw(y + 1, left + from, &clip(&"#".repeat(count), from));
+                attroff(COLOR_PAIR(WordColor::Red as i16));
+            }
+        }
+        y += 2;
+    }
+
+    if !mini_board {
+        return;
+    }
+    // 고른 상대가 없거나 탈락했으면 남아 있는 첫 상대
+    let shown = match target {
+        Target::Player(index) if players[index].out.is_none() => Some(index),
+        _ => opponents
+            .iter()
+            .copied()
+            .find(|&index| players[index].out.is_none()),
+    };
+    let index = match shown {
+        Some(index) => index,
+        None => return,
+    };
+    let title = format!("-- {} ", players[index].name);
+    attron(COLOR_PAIR(WordColor::Yellow as i16));
+    mvprintw(
+        mini_top,
+        left,
+        &clip(
+            &format!("{:-<width$}", title, width = PANEL_WIDTH as usize),
+            0,
+        ),
+    );
+    attroff(COLOR_PAIR(WordColor::Yellow as i16));
+    if let Some(board) = &boards[index] {
+        for word in &board.words {
+            // 게임 화면의 단어 영역(1 ~ HEIGHT-3)을 미니 보드 크기로 줄인다
+            let y = (word.y - 1) * (mini_height - 1) / (HEIGHT - 2);
+            if word.y < 1 || y >= mini_height - 1 {
+                continue;
+            }
+            let x = word.x * PANEL_WIDTH / WIDTH;
+            let color = word.color as i16;
+            attron(COLOR_PAIR(color));
+            mvprintw(mini_top + 1 + y, left + x, &clip(&word.text, x));
+            attroff(COLOR_PAIR(color));
+        }
+        mvprintw(HEIGHT - 1, left, &clip(&format!("> {}", board.input), 0));
+    }
+}
+
+// 이름과 점수, 탈락했거나 끊긴 경우 그 상태
+fn player_status_string(player: &PlayerStatus) -> String {
+    match (&player.out, player.away) {
+        (Some(_), _) => format!("{}:OUT({})", player.name, player.score),
+        (None, Some(until)) => format!(
+            "{}:AWAY {}s",
+            player.name,
+            until.saturating_duration_since(Instant::now()).as_secs()
+        ),
+        (None, None) => format!("{}:{}", player.name, player.score),
+    }
+}
@@ -1,15 +1,172 @@
 use clap::Parser;
 
-use raingame::Message;
+use raingame::{
+    parse_seconds, read_message, spawn_message_reader, vocab_hash, write_message, Board, GameMode,
+    Message, ProtocolError, Rating, RoomInfo, Target, VocabGenerator, MAX_ATTACK_STRENGTH,
+    PROTOCOL_VERSION,
+};
+
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 use tokio::{
-    io::{AsyncReadExt, AsyncWriteExt},
+    io::AsyncWriteExt,
     net::{TcpListener, TcpStream},
-    sync::mpsc::{self, Receiver, Sender},
+    sync::mpsc::{self, error::TrySendError, Receiver, Sender},
+    task::JoinSet,
+    time::{interval, sleep, timeout, Instant, MissedTickBehavior},
 };
 
+// 접속 후 Hello를 기다리는 시간
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(5);
+
+// 하트비트와 재접속 설정
+#[derive(Clone, Copy, Debug)]
+struct Heartbeat {
+    ping_interval: Duration,
+    timeout: Duration,
+    grace: Duration,
+}
+
+// 접속한 클라이언트에게 보내는 vocab과 게임 모드
+struct GameData {
+    vocab_digest: String,
+    vocab_contents: String,
+    mode_contents: String,
+}
+
+// 대기열에서 매치를 기다리는 플레이어
+struct Player {
+    socket: TcpStream,
+    client_no: usize,
+    name: String,
+}
+
+// 로비에서 받은 요청 (매치메이커가 처리)
+enum LobbyRequest {
+    Quick(Player),
+    Create {
+        host: Player,
+        private: bool,
+        password: Option<String>,
+        size: usize,
+        teams: usize,
+    },
+    Join {
+        player: Player,
+        code: String,
+        password: Option<String>,
+    },
+    List(TcpStream, usize),
+    Watch {
+        player: Player,
+        code: String,
+        password: Option<String>,
+    },
+}
+
+// 정원이 찰 때까지 플레이어를 모으는 방 (첫 번째가 방장)
+struct Room {
+    players: Vec<Player>,
+    size: usize,
+    teams: usize,
+    private: bool,
+    password: Option<String>,
+}
+
+// 진행 중인 매치 (방 목록과 관전자 접속용)
+struct Running {
+    info: RoomInfo,
+    private: bool,
+    password: Option<String>,
+    spectators: Sender<TcpStream>,
+}
+
+// 매치 코드별 진행 중인 매치
+type Matches = Arc<Mutex<HashMap<String, Running>>>;
+
+// 관전자에게 보내는 메세지 버퍼 (느린 관전자 때문에 매치가 멈추지 않도록 넘치면 버린다)
+const SPECTATOR_BUFFER: usize = 64;
+
+// 참가 코드 길이와 글자 (헷갈리는 0/O, 1/I 제외)
+const ROOM_CODE_LEN: usize = 4;
+const ROOM_CODE_CHARS: &[u8] = b"ABCDEFGHJKLMNPQRSTUVWXYZ23456789";
 
-const MAX_CLIENTS: usize = 2;
+// 세션 토큰별 재접속 채널 (진행 중인 모든 매치)
+type Sessions = Arc<Mutex<HashMap<String, Sender<TcpStream>>>>;
+
+// 처음 접속한 플레이어의 레이팅과 Elo K 계수
+const DEFAULT_RATING: i32 = 1200;
+const RATING_K: f64 = 32.0;
+
+// 빠른 매치에서 짝지을 수 있는 레이팅 차이 (대기 1초마다 넓어진다)
+const RATING_RANGE: i32 = 100;
+const RATING_RANGE_PER_SEC: i32 = 20;
+const MATCHMAKING_INTERVAL: Duration = Duration::from_secs(1);
+
+// 상대에게 전달하는 대기 중 공격 수의 상한 (클라이언트가 보낸 값을 그대로 믿지 않는다)
+const MAX_PENDING: u32 = 64;
+
+// 플레이어 이름별 레이팅 (파일에 저장)
+struct Ratings {
+    path: String,
+    ratings: HashMap<String, i32>,
+}
+
+type SharedRatings = Arc<Mutex<Ratings>>;
+
+impl Ratings {
+    // 파일이 없으면 빈 목록으로 시작한다
+    fn load(path: &str) -> Result<Self, String> {
+        let ratings = match std::fs::read_to_string(path) {
+            Ok(contents) => toml::from_str(&contents)
+                .map_err(|e| format!("Invalid ratings file {}: {}", path, e))?,
+            Err(_) => HashMap::new(),
+        };
+        Ok(Ratings {
+            path: path.to_string(),
+            ratings,
+        })
+    }
+
+    fn get(&self, name: &str) -> i32 {
+        *self.ratings.get(name).unwrap_or(&DEFAULT_RATING)
+    }
+
+    fn update(&mut self, name: &str, change: i32) {
+        let rating = self.get(name) + change;
+        self.ratings.insert(name.to_string(), rating);
+    }
+
+    fn save(&self) {
+        let contents = toml::to_string(&self.ratings).expect("ratings should be serializable");
+        if let Err(e) = std::fs::write(&self.path, contents) {
+            println!("[Server] Failed to save ratings to {}: {}", self.path, e);
+        }
+    }
+}
+
+// 빠른 매치 대기열의 플레이어
+struct Queued {
+    player: Player,
+    rating: i32,
+    since: Instant,
+}
+
+// 클라이언트 핸들러가 허브로 보내는 이벤트
+enum Event {
+    // 내 클라이언트가 보낸 게임 메세지
+    Client(Message),
+    // 내 클라이언트가 끊겨 재접속을 기다리는 중 (유예 시간)
+    Away(f32),
+    Back,
+    // 유예 시간 안에 돌아오지 않음
+    Left(&'static str),
+}
 
 #[derive(Parser, Debug)]
 struct Opts {
@@ -20,156 +177,1294 @@ struct Opts {
     // Port of the server to connect to
     #[arg(short, long, default_value = "22345")]
     port: String,
+
+    // Vocab pack to send to clients (one word, or `prompt<TAB>answer`, per line)
+    #[arg(short, long, default_value = "./config/vocab.txt")]
+    vocab: String,
+
+    // Custom game mode in ./config/modes/<name>.toml
+    #[arg(short, long)]
+    mode: Option<String>,
+
+    // Seconds between pings to each client
+    #[arg(long, default_value = "1.0", value_parser = parse_seconds)]
+    ping_interval: Duration,
+
+    // Seconds without any message before a client is considered dead
+    #[arg(long, default_value = "10.0", value_parser = parse_seconds)]
+    timeout: Duration,
+
+    // Seconds to hold a disconnected player's slot for a reconnect
+    #[arg(long, default_value = "15.0", value_parser = parse_seconds)]
+    grace: Duration,
+
+    // File to keep player ratings in
+    #[arg(long, default_value = "./ratings.toml")]
+    ratings: String,
+
+    // Largest room size a client may create
+    #[arg(long, default_value = "8")]
+    max_players: usize,
 }
 
 #[tokio::main]
 async fn main() {
     let opts = Opts::parse();
 
+    // 게임 모드 파일을 미리 검사해 두고 원문 그대로 클라이언트에게 보낸다
+    let mode_contents = match &opts.mode {
+        Some(name) => {
+            if let Err(e) = GameMode::load(name) {
+                println!("[Server] {}", e);
+                return;
+            }
+            std::fs::read_to_string(GameMode::path(name)).unwrap()
+        }
+        None => String::new(),
+    };
+
+    // vocab은 시작할 때 한 번만 읽고 해시를 계산해 둔다 (빈 팩은 클라이언트가 받을 수 없다)
+    if let Err(e) = VocabGenerator::from_file(&opts.vocab) {
+        println!("[Server] {}", e);
+        return;
+    }
+    let vocab_contents = std::fs::read_to_string(&opts.vocab).unwrap();
+    let vocab_digest = vocab_hash(vocab_contents.as_bytes());
+    println!("[Server] Vocab {} ({})", opts.vocab, vocab_digest);
+
+    let heartbeat = Heartbeat {
+        ping_interval: opts.ping_interval,
+        timeout: opts.timeout,
+        grace: opts.grace,
+    };
+    let game_data = Arc::new(GameData {
+        vocab_digest,
+        vocab_contents,
+        mode_contents,
+    });
+    let sessions: Sessions = Arc::new(Mutex::new(HashMap::new()));
+    let matches: Matches = Arc::new(Mutex::new(HashMap::new()));
+    let ratings = match Ratings::load(&opts.ratings) {
+        Ok(ratings) => Arc::new(Mutex::new(ratings)),
+        Err(e) => {
+            println!("[Server] {}", e);
+            return;
+        }
+    };
+
+    // 서버 소켓 생성 (서버가 떠 있는 동안 계속 사용)
+    let addr = format!("{}:{}", opts.host, opts.port);
+    let listener = match TcpListener::bind(&addr).await {
+        Ok(listener) => listener,
+        Err(e) => {
+            println!("[Server] Failed to listen on {}: {}", addr, e);
+            return;
+        }
+    };
+
+    // 대기열의 플레이어를 짝지어 매치를 만드는 쓰레드
+    let (queue_writer, queue_reader) = mpsc::channel::<LobbyRequest>(32);
+    tokio::spawn(matchmaker(
+        queue_reader,
+        heartbeat,
+        opts.max_players,
+        sessions.clone(),
+        ratings,
+        matches,
+    ));
+
+    // 클라이언트 접속 대기
+    println!("[Server] Waiting for clients...");
+    let mut client_no = 0;
     loop {
-        println!("[Server] Setting up a new game...");
+        let (socket, peer) = match listener.accept().await {
+            Ok(client) => client,
+            Err(e) => {
+                println!("[Server] Failed to accept client: {}", e);
+                continue;
+            }
+        };
+        client_no += 1;
+        println!("[Server] Client{} connected from {}", client_no, peer);
 
-        let mut client_sockets = Vec::new();
-        let mut client_handler = Vec::new();
+        // 핸드셰이크는 접속마다 별도 쓰레드에서 처리
+        let game_data = game_data.clone();
+        let queue_writer = queue_writer.clone();
+        let sessions = sessions.clone();
+        tokio::spawn(async move {
+            accept_client(socket, client_no, game_data, queue_writer, sessions).await;
+        });
+    }
+}
 
-        // 핸들러 채널 생성
-        let (client1_writer, client1_reader) = mpsc::channel::<Message>(8);
-        let (client2_writer, client2_reader) = mpsc::channel::<Message>(8);
+// 핸드셰이크 후 새 플레이어는 로비 요청과 함께 매치메이커로, 재접속한 플레이어는 진행 중인 매치로 보낸다
+async fn accept_client(
+    mut socket: TcpStream,
+    client_no: usize,
+    game_data: Arc<GameData>,
+    queue_writer: Sender<LobbyRequest>,
+    sessions: Sessions,
+) {
+    match greet_client(&mut socket).await {
+        Ok((name, Some(token))) => {
+            let reconnect_writer = sessions.lock().unwrap().get(&token).cloned();
+            match reconnect_writer {
+                Some(reconnect_writer) if !reconnect_writer.is_closed() => {
+                    let welcome = Message::Welcome {
+                        version: PROTOCOL_VERSION,
+                    };
+                    if let Err(e) = write_message(&mut socket, &welcome).await {
+                        println!(
+                            "[Server] Failed to resume {} (Client{}): {}",
+                            name, client_no, e
+                        );
+                        return;
+                    }
+                    // Welcome 뒤에 핸들러가 끝난 경우에도 클라이언트가 더 시도하지 않도록 거절한다
+                    match reconnect_writer.send(socket).await {
+                        Ok(()) => println!("[Server] {} reconnected as Client{}", name, client_no),
+                        Err(e) => {
+                            let mut socket = e.0;
+                            reject_client(&mut socket, "session expired").await;
+                            println!("[Server] Dropped Client{}: session expired", client_no);
+                        }
+                    }
+                }
+                _ => {
+                    reject_client(&mut socket, "session expired").await;
+                    println!("[Server] Dropped Client{}: session expired", client_no);
+                }
+            }
+        }
+        Ok((name, None)) => {
+            if let Err(e) = setup_client(
+                &mut socket,
+                client_no,
+                &game_data.vocab_digest,
+                &game_data.vocab_contents,
+                &game_data.mode_contents,
+            )
+            .await
+            {
+                println!("[Server] Dropped Client{}: {}", client_no, e);
+                return;
+            }
 
-        let channelsets = vec![
-            (client1_writer, client2_reader),
-            (client2_writer, client1_reader),
-        ];
+            // 로비 요청 (빠른 매치, 방 만들기/입장, 방 목록)
+            let request = match timeout(HANDSHAKE_TIMEOUT, read_message(&mut socket)).await {
+                Ok(Ok(request)) => request,
+                Ok(Err(e)) => {
+                    println!("[Server] Dropped Client{}: {}", client_no, e);
+                    return;
+                }
+                Err(_) => {
+                    println!("[Server] Dropped Client{}: no lobby request", client_no);
+                    return;
+                }
+            };
+            println!(
+                "[Server] Client{} is {}, requested {}",
+                client_no,
+                name,
+                request.kind()
+            );
+            let player = Player {
+                socket,
+                client_no,
+                name,
+            };
+            let request = match request {
+                Message::QuickMatch => LobbyRequest::Quick(player),
+                Message::CreateRoom {
+                    private,
+                    password,
+                    size,
+                    teams,
+                } => LobbyRequest::Create {
+                    host: player,
+                    private,
+                    password,
+                    size,
+                    teams,
+                },
+                Message::JoinRoom { code, password } => LobbyRequest::Join {
+                    player,
+                    code: code.to_uppercase(),
+                    password,
+                },
+                Message::ListRooms => LobbyRequest::List(player.socket, client_no),
+                Message::Watch { code, password } => LobbyRequest::Watch {
+                    player,
+                    code: code.to_uppercase(),
+                    password,
+                },
+                msg => {
+                    let mut socket = player.socket;
+                    reject_client(&mut socket, "expected a lobby request").await;
+                    println!(
+                        "[Server] Dropped Client{}: {}",
+                        client_no,
+                        ProtocolError::Unexpected(msg.kind())
+                    );
+                    return;
+                }
+            };
+            if queue_writer.send(request).await.is_err() {
+                println!("[Server] Matchmaker closed, dropped Client{}", client_no);
+            }
+        }
+        Err(e) => {
+            println!("[Server] Dropped Client{}: {}", client_no, e);
+        }
+    }
+}
+
+// 로비 요청을 처리한다
+// 빠른 매치는 레이팅이 비슷한 두 명씩, 방은 방장과 참가 코드로 들어온 플레이어를 짝지어 매치를 시작한다
+async fn matchmaker(
+    mut queue_reader: Receiver<LobbyRequest>,
+    heartbeat: Heartbeat,
+    max_players: usize,
+    sessions: Sessions,
+    ratings: SharedRatings,
+    matches: Matches,
+) {
+    let mut waiting: Vec<Queued> = Vec::new();
+    let mut rooms: HashMap<String, Room> = HashMap::new();
+    let mut match_no = 0;
+
+    // 기다리는 동안 넓어진 레이팅 범위로 다시 짝을 찾는다
+    let mut tick = interval(MATCHMAKING_INTERVAL);
+    tick.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+    loop {
+        tokio::select! {
+            request = queue_reader.recv() => {
+                match request {
+                    Some(LobbyRequest::Quick(mut player)) => {
+                        if !send_waiting(&mut player).await {
+                            continue;
+                        }
+                        let rating = ratings.lock().unwrap().get(&player.name);
+                        waiting.push(Queued {
+                            player,
+                            rating,
+                            since: Instant::now(),
+                        });
+                    }
+                    Some(LobbyRequest::Create {
+                        mut host,
+                        private,
+                        password,
+                        size,
+                        teams,
+                    }) => {
+                        let reason = if !(2..=max_players).contains(&size) {
+                            Some(format!("room size must be 2 to {}", max_players))
+                        } else if teams == 1 || (teams > 1 && size % teams != 0) {
+                            Some(format!("{} players cannot be split into {} teams", size, teams))
+                        } else {
+                            None
+                        };
+                        if let Some(reason) = reason {
+                            reject_client(&mut host.socket, &reason).await;
+                            println!("[Server] Client{} failed to create room: {}", host.client_no, reason);
+                            continue;
+                        }
+                        let code = new_room_code(&rooms, &matches);
+                        let created = Message::RoomCreated { code: code.clone() };
+                        if write_message(&mut host.socket, &created).await.is_err()
+                            || !send_waiting(&mut host).await
+                        {
+                            continue;
+                        }
+                        println!(
+                            "[Server] Client{} created room {} for {}{}",
+                            host.client_no,
+                            code,
+                            size,
+                            if private { " (private)" } else { "" }
+                        );
+                        rooms.insert(
+                            code,
+                            Room {
+                                players: vec![host],
+                                size,
+                                teams,
+                                private,
+                                password,
+                            },
+                        );
+                    }
+                    Some(LobbyRequest::Join {
+                        mut player,
+                        code,
+                        password,
+                    }) => {
+                        prune_rooms(&mut rooms).await;
+                        let reason = match rooms.get(&code) {
+                            None => Some("room not found"),
+                            Some(room) if room.password.is_some() && room.password != password => {
+                                Some("wrong password")
+                            }
+                            Some(_) => None,
+                        };
+                        if let Some(reason) = reason {
+                            reject_client(&mut player.socket, reason).await;
+                            println!(
+                                "[Server] Client{} failed to join room {}: {}",
+                                player.client_no, code, reason
+                            );
+                            continue;
+                        }
+
+                        let room = rooms.get_mut(&code).unwrap();
+                        println!("[Server] Client{} joined room {}", player.client_no, code);
+                        room.players.push(player);
+
+                        // 정원이 차면 게임 시작, 아니면 나머지를 기다린다
+                        if room.players.len() < room.size {
+                            let player = room.players.last_mut().unwrap();
+                            if !send_waiting(player).await {
+                                room.players.pop();
+                            }
+                            continue;
+                        }
+                        let room = rooms.remove(&code).unwrap();
+                        match_no += 1;
+                        start_match(
+                            match_no,
+                            code,
+                            room,
+                            heartbeat,
+                            sessions.clone(),
+                            ratings.clone(),
+                            matches.clone(),
+                        );
+                    }
+                    Some(LobbyRequest::Watch {
+                        player,
+                        code,
+                        password,
+                    }) => {
+                        let spectators = match matches.lock().unwrap().get(&code) {
+                            None => Err("match not found"),
+                            Some(running) if running.password.is_some() && running.password != password => {
+                                Err("wrong password")
+                            }
+                            Some(running) => Ok(running.spectators.clone()),
+                        };
+                        // 매치가 방금 끝났으면 허브가 관전자를 더 받지 않는다
+                        let result = match spectators {
+                            Ok(spectators) => spectators
+                                .send(player.socket)
+                                .await
+                                .map_err(|e| (e.0, "match not found")),
+                            Err(reason) => Err((player.socket, reason)),
+                        };
+                        match result {
+                            Ok(()) => println!("[Server] Client{} is watching {}", player.client_no, code),
+                            Err((mut socket, reason)) => {
+                                reject_client(&mut socket, reason).await;
+                                println!(
+                                    "[Server] Client{} failed to watch {}: {}",
+                                    player.client_no, code, reason
+                                );
+                            }
+                        }
+                    }
+                    Some(LobbyRequest::List(mut socket, client_no)) => {
+                        prune_rooms(&mut rooms).await;
+                        // 비공개 방은 목록에 보이지 않는다
+                        let mut list = rooms
+                            .iter()
+                            .filter(|(_, room)| !room.private)
+                            .map(|(code, room)| RoomInfo {
+                                code: code.clone(),
+                                host: room.players[0].name.clone(),
+                                locked: room.password.is_some(),
+                                players: room.players.len(),
+                                size: room.size,
+                                teams: room.teams,
+                                playing: false,
+                            })
+                            .collect::<Vec<RoomInfo>>();
+                        // 진행 중인 공개 매치는 관전할 수 있도록 함께 보여준다
+                        list.extend(
+                            matches
+                                .lock()
+                                .unwrap()
+                                .values()
+                                .filter(|running| !running.private)
+                                .map(|running| running.info.clone()),
+                        );
+                        list.sort_by(|a, b| a.code.cmp(&b.code));
+                        let msg = Message::Rooms { rooms: list };
+                        if let Err(e) = write_message(&mut socket, &msg).await {
+                            println!("[Server] Failed to send rooms to Client{}: {}", client_no, e);
+                        }
+                    }
+                    None => break,
+                }
+            }
+            _ = tick.tick() => {}
+        }
 
-        // 서버 소켓 생성
-        let addr = format!("{}:{}", opts.host, opts.port);
-        let listener = TcpListener::bind(addr).await.unwrap();
+        prune_rooms(&mut rooms).await;
 
-        // 클라이언트 접속 대기
-        println!("[Server] Waiting for clients...");
-        for index in 0..MAX_CLIENTS {
-            let (mut socket, _) = listener.accept().await.unwrap(); // 클라이언트 연결 대기
+        // 빠른 매치 대기열에서 짝 찾기 (기다리다 나간 플레이어는 먼저 뺀다)
+        let mut index = 0;
+        while index < waiting.len() {
+            if is_connected(&waiting[index].player).await {
+                index += 1;
+            } else {
+                let left = waiting.remove(index);
+                println!("[Server] Client{} left the queue", left.player.client_no);
+            }
+        }
+        while let Some((first, second)) = find_pair(
+            &waiting
+                .iter()
+                .map(|queued| (queued.rating, queued.since))
+                .collect::<Vec<_>>(),
+        ) {
+            let second = waiting.remove(second);
+            let first = waiting.remove(first);
             println!(
-                "[Server] Client connected from {}",
-                socket.peer_addr().unwrap()
+                "[Server] Paired {} ({}) with {} ({})",
+                first.player.name, first.rating, second.player.name, second.rating
+            );
+            match_no += 1;
+            let code = new_room_code(&rooms, &matches);
+            let room = Room {
+                players: vec![first.player, second.player],
+                size: 2,
+                teams: 0,
+                private: false,
+                password: None,
+            };
+            start_match(
+                match_no,
+                code,
+                room,
+                heartbeat,
+                sessions.clone(),
+                ratings.clone(),
+                matches.clone(),
             );
+        }
+    }
+}
+
+// 오래 기다린 플레이어부터 레이팅 차이가 허용 범위 안인 가장 가까운 상대를 찾는다
+fn find_pair(waiting: &[(i32, Instant)]) -> Option<(usize, usize)> {
+    for (i, &(rating, since)) in waiting.iter().enumerate() {
+        let partner = waiting
+            .iter()
+            .enumerate()
+            .skip(i + 1)
+            .filter(|(_, &(other, other_since))| {
+                let range = rating_range(since).max(rating_range(other_since));
+                (rating - other).abs() <= range
+            })
+            .min_by_key(|(_, &(other, _))| (rating - other).abs());
+        if let Some((j, _)) = partner {
+            return Some((i, j));
+        }
+    }
+    None
+}
 
-            // // vocab 전송
-            let mut file = tokio::fs::File::open("./config/vocab.txt").await.expect("Need ./config/vocab.txt for game");
-            let mut contents = vec![];
+// 오래 기다릴수록 넓어지는 레이팅 범위
+fn rating_range(since: Instant) -> i32 {
+    RATING_RANGE + RATING_RANGE_PER_SEC * since.elapsed().as_secs() as i32
+}
+
+// Elo 기대 승률로 이겼을 때와 졌을 때의 레이팅 변화량을 계산한다
+fn rating_stakes(rating: i32, opponent: i32) -> (i32, i32) {
+    let expected = 1.0 / (1.0 + 10f64.powf((opponent - rating) as f64 / 400.0));
+    let on_win = (RATING_K * (1.0 - expected)).round() as i32;
+    let on_loss = (RATING_K * -expected).round() as i32;
+    (on_win, on_loss)
+}
 
-            let n = file.read_to_end(&mut contents).await.unwrap();
-            socket.write_u32(n as u32).await.unwrap();
-            
-            socket.write_all(&contents).await.unwrap();
-            println!("[Server] Vocab sent to Client{}", index + 1);
+// 기다리는 동안 클라이언트가 연결을 끊었는지 확인한다
+// 기다리는 클라이언트는 아무것도 보내지 않으므로 읽을 게 없으면 연결된 것으로 본다
+async fn is_connected(player: &Player) -> bool {
+    let mut buf = [0; 1];
+    match timeout(Duration::ZERO, player.socket.peek(&mut buf)).await {
+        Ok(Ok(0)) | Ok(Err(_)) => false, // 연결 끊김
+        Ok(Ok(_)) | Err(_) => true,
+    }
+}
+
+// 방에서 기다리다 나간 플레이어를 빼고, 아무도 남지 않은 방은 닫는다
+async fn prune_rooms(rooms: &mut HashMap<String, Room>) {
+    for (code, room) in rooms.iter_mut() {
+        let mut index = 0;
+        while index < room.players.len() {
+            if is_connected(&room.players[index]).await {
+                index += 1;
+            } else {
+                let left = room.players.remove(index);
+                println!("[Server] Client{} left room {}", left.client_no, code);
+            }
+        }
+    }
+    rooms.retain(|code, room| {
+        if room.players.is_empty() {
+            println!("[Server] Closed empty room {}", code);
+        }
+        !room.players.is_empty()
+    });
+}
 
-            let msg = Message::Waiting;
-            socket.write_all(&[msg as u8]).await.unwrap(); // 클라이언트에게 상대방 접속 대기
-            println!("[Server] SENT Message::Waiting to Client{}", index + 1);
+// 상대를 기다리는 클라이언트에게 Waiting 전송 (실패하면 false)
+async fn send_waiting(player: &mut Player) -> bool {
+    match write_message(&mut player.socket, &Message::Waiting).await {
+        Ok(()) => {
+            println!(
+                "[Server] SENT Message::Waiting to Client{}",
+                player.client_no
+            );
+            true
+        }
+        Err(e) => {
+            println!("[Server] Dropped Client{}: {}", player.client_no, e);
+            false
+        }
+    }
+}
 
-            // socket 소유권이 clients vector로 이동
-            client_sockets.push(socket);
+// 지금 열려 있는 방, 진행 중인 매치와 겹치지 않는 참가 코드
+fn new_room_code(rooms: &HashMap<String, Room>, matches: &Matches) -> String {
+    let mut rng = rand::thread_rng();
+    let matches = matches.lock().unwrap();
+    loop {
+        let code = (0..ROOM_CODE_LEN)
+            .map(|_| *ROOM_CODE_CHARS.choose(&mut rng).unwrap() as char)
+            .collect::<String>();
+        if !rooms.contains_key(&code) && !matches.contains_key(&code) {
+            return code;
         }
+    }
+}
 
-        if client_sockets.len() == MAX_CLIENTS {
-            println!("[Server] All clients connected");
+// 매치를 진행 중인 매치 목록에 올리고 시작한다 (끝나면 목록에서 뺀다)
+fn start_match(
+    match_no: usize,
+    code: String,
+    room: Room,
+    heartbeat: Heartbeat,
+    sessions: Sessions,
+    ratings: SharedRatings,
+    matches: Matches,
+) {
+    // 팀전이면 들어온 순서대로 번갈아 팀을 나누고, 개인전이면 모두 다른 팀
+    let teams = (0..room.size)
+        .map(|index| {
+            if room.teams == 0 {
+                index
+            } else {
+                index % room.teams
+            }
+        })
+        .collect::<Vec<usize>>();
+    let (spectators, spectators_reader) = mpsc::channel::<TcpStream>(4);
+    let running = Running {
+        info: RoomInfo {
+            code: code.clone(),
+            host: room.players[0].name.clone(),
+            locked: room.password.is_some(),
+            players: room.players.len(),
+            size: room.size,
+            teams: room.teams,
+            playing: true,
+        },
+        private: room.private,
+        password: room.password,
+        spectators,
+    };
+    matches.lock().unwrap().insert(code.clone(), running);
 
-            // 클라이언트 핸들러를 각각의 쓰레드로 분리
-            for (index, (socket, channelset)) in client_sockets
-                .into_iter()
-                .zip(channelsets.into_iter())
-                .enumerate()
-            {
-                let handle = tokio::spawn(async move {
-                    handler(socket, index + 1, channelset).await;
-                });
-                client_handler.push(handle);
+    tokio::spawn(async move {
+        run_match(
+            match_no,
+            room.players,
+            teams,
+            spectators_reader,
+            heartbeat,
+            sessions,
+            ratings,
+        )
+        .await;
+        matches.lock().unwrap().remove(&code);
+    });
+}
+
+// 매치 하나를 진행한다 (매치마다 별도 쓰레드)
+async fn run_match(
+    match_no: usize,
+    players: Vec<Player>,
+    teams: Vec<usize>,
+    spectators: Receiver<TcpStream>,
+    heartbeat: Heartbeat,
+    sessions: Sessions,
+    ratings: SharedRatings,
+) {
+    let names = players
+        .iter()
+        .map(|player| player.name.clone())
+        .collect::<Vec<String>>();
+    let count = names.len();
+
+    // 시작할 때의 레이팅으로 결과에 따른 변화량을 정해 둔다 (상대 팀이 여러 명이면 평균과 비교)
+    let before = {
+        let ratings = ratings.lock().unwrap();
+        names
+            .iter()
+            .map(|name| ratings.get(name))
+            .collect::<Vec<i32>>()
+    };
+    let stakes = (0..count)
+        .map(|index| {
+            let opponents = (0..count)
+                .filter(|&other| teams[other] != teams[index])
+                .map(|other| before[other])
+                .collect::<Vec<i32>>();
+            let opponent = opponents.iter().sum::<i32>() / opponents.len() as i32;
+            let (on_win, on_loss) = rating_stakes(before[index], opponent);
+            Rating {
+                before: before[index],
+                opponent,
+                on_win,
+                on_loss,
             }
+        })
+        .collect::<Vec<Rating>>();
+    println!(
+        "[Server] [Match{}] {} started",
+        match_no,
+        names
+            .iter()
+            .zip(&before)
+            .map(|(name, rating)| format!("{} ({})", name, rating))
+            .collect::<Vec<String>>()
+            .join(" vs ")
+    );
+
+    // 핸들러 채널 생성
+    // 모든 핸들러 -(event)> 허브 -(message)> 각 핸들러
+    let (event_writer, event_reader) = mpsc::channel::<(usize, Event)>(32);
+
+    // 클라이언트 핸들러를 각각의 쓰레드로 분리
+    let mut client_handler = JoinSet::new();
+    let mut client_writers = Vec::new();
+    for (index, player) in players.into_iter().enumerate() {
+        let (client_writer, client_reader) = mpsc::channel::<Message>(8);
+        client_writers.push(client_writer);
 
-            // 클라이언트 핸들러 쓰레드 종료 대기
-            for handler in client_handler {
-                handler.await.unwrap();
+        // 세션 토큰별 재접속 채널
+        let token = new_session_token();
+        let (reconnect_writer, reconnect_reader) = mpsc::channel::<TcpStream>(1);
+        sessions
+            .lock()
+            .unwrap()
+            .insert(token.clone(), reconnect_writer);
+
+        let start = Message::GameStart {
+            players: names.clone(),
+            teams: teams.clone(),
+            you: index,
+            token: token.clone(),
+            grace: heartbeat.grace.as_secs_f32(),
+            rating: stakes[index],
+        };
+        let handler = handler(
+            player.socket,
+            player.client_no,
+            index,
+            start,
+            heartbeat,
+            reconnect_reader,
+            (event_writer.clone(), client_reader),
+        );
+        // 핸들러가 끝나면(유예 시간 초과 포함) 그 세션으로는 더 이상 돌아올 수 없다
+        let sessions = sessions.clone();
+        client_handler.spawn(async move {
+            handler.await;
+            sessions.lock().unwrap().remove(&token);
+        });
+    }
+    drop(event_writer);
+
+    let winner = hub(
+        match_no,
+        &names,
+        &teams,
+        event_reader,
+        client_writers,
+        spectators,
+    )
+    .await;
+
+    // 클라이언트 핸들러 쓰레드 종료 대기
+    while let Some(result) = client_handler.join_next().await {
+        if let Err(e) = result {
+            println!("[Server] [Match{}] Client handler failed: {}", match_no, e);
+        }
+    }
+
+    // 이긴 팀은 이긴 만큼, 나머지는 진 만큼 레이팅에 반영한다
+    match winner {
+        Some(winner) => {
+            let mut ratings = ratings.lock().unwrap();
+            for (index, name) in names.iter().enumerate() {
+                let change = if teams[index] == winner {
+                    stakes[index].on_win
+                } else {
+                    stakes[index].on_loss
+                };
+                ratings.update(name, change);
             }
+            ratings.save();
+            let winners = (0..count)
+                .filter(|&index| teams[index] == winner)
+                .map(|index| names[index].clone())
+                .collect::<Vec<String>>();
+            println!(
+                "[Server] [Match{}] Finished, {} won",
+                match_no,
+                winners.join(", ")
+            );
         }
+        None => println!("[Server] [Match{}] Finished without a result", match_no),
+    }
+}
+
+// 매치의 플레이어들 사이에서 공격과 상태를 전달하고 이긴 팀을 정한다
+// 마지막까지 남은 팀, 또는 게임 모드의 승리 조건을 먼저 달성한 플레이어의 팀이 이긴다
+async fn hub(
+    match_no: usize,
+    names: &[String],
+    teams: &[usize],
+    mut events: Receiver<(usize, Event)>,
+    clients: Vec<Sender<Message>>,
+    mut spectators: Receiver<TcpStream>,
+) -> Option<usize> {
+    let count = clients.len();
+    let mut scores = vec![0; count];
+    let mut alive = vec![true; count];
+    let mut targets = vec![Target::Random; count];
+    let mut mini_boards = vec![false; count];
+    let mut outs: Vec<Option<Message>> = vec![None; count];
+    let mut boards: Vec<Option<Board>> = vec![None; count];
+    let mut watchers: Vec<Sender<Message>> = Vec::new();
 
-        println!("[Server] Game Finished!")
+    let winner = loop {
+        let (from, event) = tokio::select! {
+            event = events.recv() => match event {
+                Some(event) => event,
+                None => break None, // 모든 핸들러 종료
+            },
+            Some(socket) = spectators.recv() => {
+                // 새 관전자에게 플레이어 목록과 지금까지의 화면, 탈락한 플레이어를 보낸다
+                let watcher = spawn_spectator(socket);
+                let start = Message::Spectate {
+                    players: names.to_vec(),
+                    teams: teams.to_vec(),
+                };
+                _ = watcher.try_send(start);
+                for (player, board) in boards.iter().enumerate() {
+                    if let Some(board) = board {
+                        let board = board.clone();
+                        _ = watcher.try_send(Message::PlayerBoard { player, board });
+                    }
+                }
+                for msg in outs.iter().flatten() {
+                    _ = watcher.try_send(msg.clone());
+                }
+                watchers.push(watcher);
+                continue;
+            }
+        };
+        let out = match event {
+            Event::Client(Message::Attacked {
+                word,
+                kind,
+                strength,
+            }) => {
+                if !alive[from] {
+                    continue;
+                }
+                let to = match pick_target(from, targets[from], teams, &scores, &alive) {
+                    Some(to) => to,
+                    None => continue,
+                };
+
+                // 고른 상대의 팀에서 남은 플레이어들에게 세기를 나눠 보낸다 (나머지는 무작위로 1씩 더)
+                let strength = strength.min(MAX_ATTACK_STRENGTH);
+                let mut members = (0..count)
+                    .filter(|&index| teams[index] == teams[to] && alive[index])
+                    .collect::<Vec<usize>>();
+                members.shuffle(&mut rand::thread_rng());
+                let share = strength / members.len() as u32;
+                let extra = strength as usize % members.len();
+                for (rank, &member) in members.iter().enumerate() {
+                    let strength = share + if rank < extra { 1 } else { 0 };
+                    if strength == 0 {
+                        continue;
+                    }
+                    println!(
+                        "[Server] [Match{}] {} attacks {} with {} x{}",
+                        match_no,
+                        names[from],
+                        names[member],
+                        kind.get_name(),
+                        strength
+                    );
+                    let msg = Message::Attacked {
+                        word: word.clone(),
+                        kind,
+                        strength,
+                    };
+                    _ = clients[member].send(msg).await;
+                }
+                continue;
+            }
+            Event::Client(Message::Target { target }) => {
+                targets[from] = target;
+                continue;
+            }
+            Event::Client(Message::MiniBoard { enabled }) => {
+                mini_boards[from] = enabled;
+                continue;
+            }
+            Event::Client(Message::State {
+                score,
+                life,
+                combo,
+                pending,
+            }) => {
+                scores[from] = score;
+                let msg = Message::PlayerState {
+                    player: from,
+                    score,
+                    life: life.max(0),
+                    combo: combo.max(0),
+                    pending: pending.min(MAX_PENDING),
+                };
+                watch(&mut watchers, &msg);
+                broadcast(&clients, from, msg).await;
+                continue;
+            }
+            Event::Client(Message::Board { board }) => {
+                boards[from] = Some(board.clone());
+                let msg = Message::PlayerBoard {
+                    player: from,
+                    board,
+                };
+                watch(&mut watchers, &msg);
+                // 미니 보드에 이 플레이어를 보여주는 클라이언트에게만 보낸다
+                // 화면은 금방 다시 오므로 밀려 있으면 버린다 (매치 전체를 멈추지 않는다)
+                for member in 0..count {
+                    if mini_boards[member]
+                        && alive[member]
+                        && shown_board(member, targets[member], teams, &alive) == Some(from)
+                    {
+                        _ = clients[member].try_send(msg.clone());
+                    }
+                }
+                continue;
+            }
+            Event::Client(Message::GameOver { score }) => {
+                scores[from] = score;
+                "game over"
+            }
+            Event::Client(Message::Victory { score }) => {
+                if !alive[from] {
+                    continue;
+                }
+                scores[from] = score;
+                break Some(teams[from]);
+            }
+            Event::Client(_) => continue, // 위 메세지 타입 외에는 무시
+            Event::Away(grace) => {
+                if alive[from] {
+                    let msg = Message::PlayerAway {
+                        player: from,
+                        grace,
+                    };
+                    watch(&mut watchers, &msg);
+                    broadcast(&clients, from, msg).await;
+                }
+                continue;
+            }
+            Event::Back => {
+                if alive[from] {
+                    let msg = Message::PlayerBack { player: from };
+                    watch(&mut watchers, &msg);
+                    broadcast(&clients, from, msg).await;
+                }
+                continue;
+            }
+            Event::Left(reason) => reason,
+        };
+
+        // 탈락한 플레이어는 매치가 끝날 때까지 관전한다
+        if !alive[from] {
+            continue;
+        }
+        alive[from] = false;
+        println!(
+            "[Server] [Match{}] {} is out ({})",
+            match_no, names[from], out
+        );
+        let msg = Message::PlayerOut {
+            player: from,
+            score: scores[from],
+            reason: out.to_string(),
+        };
+        outs[from] = Some(msg.clone());
+        watch(&mut watchers, &msg);
+        broadcast(&clients, from, msg).await;
+
+        // 남은 플레이어가 모두 같은 팀이면 그 팀의 승리
+        let mut alive_teams = (0..count)
+            .filter(|&index| alive[index])
+            .map(|index| teams[index])
+            .collect::<Vec<usize>>();
+        alive_teams.sort_unstable();
+        alive_teams.dedup();
+        match alive_teams[..] {
+            [] => break None,
+            [team] => break Some(team),
+            _ => {}
+        }
+    };
+
+    watch(&mut watchers, &Message::MatchOver { winner });
+    for client in &clients {
+        _ = client.send(Message::MatchOver { winner }).await;
+    }
+    winner
+}
+
+// 관전자 모두에게 전달 (연결이 끊긴 관전자는 빼고, 버퍼가 찬 관전자는 이번 메세지를 건너뛴다)
+fn watch(watchers: &mut Vec<Sender<Message>>, msg: &Message) {
+    watchers
+        .retain(|watcher| !matches!(watcher.try_send(msg.clone()), Err(TrySendError::Closed(_))));
+}
+
+// 관전자 소켓에 메세지를 쓰는 쓰레드 (MatchOver를 보내면 끝난다)
+fn spawn_spectator(mut socket: TcpStream) -> Sender<Message> {
+    let (writer, mut reader) = mpsc::channel::<Message>(SPECTATOR_BUFFER);
+    tokio::spawn(async move {
+        while let Some(msg) = reader.recv().await {
+            let over = matches!(msg, Message::MatchOver { .. });
+            if write_message(&mut socket, &msg).await.is_err() || over {
+                break;
+            }
+        }
+    });
+    writer
+}
+
+// 보낸 플레이어를 뺀 모두에게 전달 (이미 끝난 핸들러는 무시)
+async fn broadcast(clients: &[Sender<Message>], from: usize, msg: Message) {
+    for (index, client) in clients.iter().enumerate() {
+        if index != from {
+            _ = client.send(msg.clone()).await;
+        }
+    }
+}
+
+// 미니 보드에 보이는 상대 (고른 상대가 없거나 탈락했으면 남아 있는 첫 상대)
+// 클라이언트의 draw_opponent_panel과 같은 규칙이다
+fn shown_board(member: usize, target: Target, teams: &[usize], alive: &[bool]) -> Option<usize> {
+    match target {
+        Target::Player(index) if alive.get(index) == Some(&true) => Some(index),
+        _ => (0..alive.len()).find(|&index| teams[index] != teams[member] && alive[index]),
+    }
+}
+
+// 공격자가 고른 상대 팀의 플레이어 (고른 플레이어가 이미 탈락했으면 무작위)
+fn pick_target(
+    from: usize,
+    target: Target,
+    teams: &[usize],
+    scores: &[i32],
+    alive: &[bool],
+) -> Option<usize> {
+    let candidates = (0..alive.len())
+        .filter(|&index| teams[index] != teams[from] && alive[index])
+        .collect::<Vec<usize>>();
+    match target {
+        Target::Player(index) if candidates.contains(&index) => Some(index),
+        Target::LowestScore => candidates.into_iter().min_by_key(|&index| scores[index]),
+        _ => candidates.choose(&mut rand::thread_rng()).copied(),
+    }
+}
+
+// Hello를 확인하고 플레이어 이름과 세션 토큰(재접속인 경우)을 돌려준다
+async fn greet_client(socket: &mut TcpStream) -> Result<(String, Option<String>), ProtocolError> {
+    // Hello 확인 (구버전 클라이언트는 Hello를 보내지 않으므로 시간 제한을 둔다)
+    let hello = match timeout(HANDSHAKE_TIMEOUT, read_message(socket)).await {
+        Ok(msg) => msg?,
+        Err(_) => {
+            return Err(ProtocolError::Incompatible(
+                "no hello within handshake timeout".to_string(),
+            ))
+        }
+    };
+    match hello.check_hello() {
+        Ok(hello) => Ok(hello),
+        Err(e) => {
+            reject_client(socket, &e.to_string()).await;
+            Err(e)
+        }
     }
 }
 
+async fn reject_client(socket: &mut TcpStream, reason: &str) {
+    let reject = Message::Reject {
+        reason: reason.to_string(),
+    };
+    _ = write_message(socket, &reject).await;
+}
+
+// vocab 및 게임 모드 전송
+async fn setup_client(
+    socket: &mut TcpStream,
+    client_no: usize,
+    vocab_digest: &str,
+    vocab_contents: &str,
+    mode_contents: &str,
+) -> Result<(), ProtocolError> {
+    let welcome = Message::Welcome {
+        version: PROTOCOL_VERSION,
+    };
+    write_message(socket, &welcome).await?;
+
+    // vocab 해시 전송, 클라이언트 캐시에 없는 경우에만 vocab 전송
+    let digest = Message::VocabHash {
+        digest: vocab_digest.to_string(),
+    };
+    write_message(socket, &digest).await?;
+    match read_message(socket).await? {
+        Message::VocabRequest { send: true } => {
+            let vocab = Message::Vocab {
+                contents: vocab_contents.to_string(),
+            };
+            write_message(socket, &vocab).await?;
+            println!("[Server] Vocab sent to Client{}", client_no);
+        }
+        Message::VocabRequest { send: false } => {
+            println!("[Server] Client{} has vocab cached", client_no);
+        }
+        msg => return Err(ProtocolError::Unexpected(msg.kind())),
+    }
+
+    // 게임 모드 전송 (비어 있으면 기본 게임)
+    let mode = Message::Mode {
+        contents: mode_contents.to_string(),
+    };
+    write_message(socket, &mode).await?;
+
+    Ok(())
+}
+
+fn new_session_token() -> String {
+    let mut rng = rand::thread_rng();
+    format!("{:016x}{:016x}", rng.gen::<u64>(), rng.gen::<u64>())
+}
+
 // 클라이언트 핸들러
 async fn handler(
-    mut socket: TcpStream,
+    socket: TcpStream,
     client_no: usize,
-    channel: (Sender<Message>, Receiver<Message>),
+    index: usize,
+    start: Message,
+    heartbeat: Heartbeat,
+    mut reconnects: Receiver<TcpStream>,
+    channel: (Sender<(usize, Event)>, Receiver<Message>),
 ) {
-    let mut buf = [0; 1];
-    let (opponent_writer, mut my_reader) = channel;
+    let mut ping = interval(heartbeat.ping_interval);
+    ping.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    let mut last_seen = Instant::now();
 
-    // 게임 시작 메세지를 각 클라이언트에게 전송
-    let msg = Message::GameStart;
-    socket
-        .write_all(&[msg as u8])
-        .await
-        .expect("Client should be connected");
+    let (reader, mut writer) = socket.into_split();
+    let mut messages = spawn_message_reader(reader);
+    let (hub_writer, mut my_reader) = channel;
 
-    println!(
-        "[Server] [Client{} Handler] SENT Message::GameStart to Client{}",
-        client_no, client_no
-    );
+    // 게임 시작 메세지를 각 클라이언트에게 전송
+    // 시작하자마자 끊긴 경우에도 재접속을 기다린다
+    let mut lost = match write_message(&mut writer, &start).await {
+        Ok(()) => {
+            println!(
+                "[Server] [Client{} Handler] SENT Message::GameStart to Client{}",
+                client_no, client_no
+            );
+            None
+        }
+        Err(e) => {
+            println!(
+                "[Server] [Client{} Handler] Failed to start game: {}",
+                client_no, e
+            );
+            Some("disconnected")
+        }
+    };
 
     loop {
+        // 내 클라이언트가 끊기면 유예 시간 동안 재접속을 기다린다
+        if let Some(reason) = lost.take() {
+            let grace = heartbeat.grace.as_secs_f32();
+            _ = hub_writer.send((index, Event::Away(grace))).await;
+            println!(
+                "[Server] [Client{} Handler] Client{} is away, waiting {:?} for reconnect",
+                client_no, client_no, heartbeat.grace
+            );
+
+            let mut missed = Vec::new();
+            match wait_for_reconnect(
+                &mut reconnects,
+                &mut my_reader,
+                heartbeat.grace,
+                &mut missed,
+            )
+            .await
+            {
+                Away::Resumed(socket) => {
+                    let (reader, new_writer) = socket.into_split();
+                    messages = spawn_message_reader(reader);
+                    writer = new_writer;
+                    last_seen = Instant::now();
+                    println!(
+                        "[Server] [Client{} Handler] Client{} is back",
+                        client_no, client_no
+                    );
+
+                    // 재접속을 알리고 그동안 받지 못한 메세지를 보낸다
+                    missed.insert(0, Message::Resumed);
+                    for msg in &missed {
+                        if write_message(&mut writer, msg).await.is_err() {
+                            lost = Some(reason);
+                            break;
+                        }
+                    }
+                    if lost.is_some() {
+                        continue;
+                    }
+                    _ = hub_writer.send((index, Event::Back)).await;
+                }
+                Away::Expired => {
+                    println!(
+                        "[Server] [Client{} Handler] Client{} did not come back",
+                        client_no, client_no
+                    );
+                    _ = hub_writer.send((index, Event::Left(reason))).await;
+                    break; // 클라이언트 핸들러 종료
+                }
+                Away::Ended => {
+                    println!(
+                        "[Server] [Client{} Handler] Match ended while Client{} was away",
+                        client_no, client_no
+                    );
+                    break; // 클라이언트 핸들러 종료
+                }
+            }
+        }
+
         tokio::select! {
-            // 내 클라이언트에서 발생한 메세지를 상대 클라이언트에게 전달
-            // MyClient -(TCP)> MyClientHandler -(channel)> OpponentClientHandler
-            nbytes = socket.read_exact(&mut buf) => {
-                match nbytes {
-                    Ok(_) => {
-                        let client_msg = Message::from(buf[0]);
-                        println!("[Server] [Client{} Handler] RELAY message from Client{} to opponent client handler: {:?}", client_no, client_no, client_msg);
+            // 내 클라이언트에서 발생한 메세지를 허브에게 전달
+            // MyClient -(TCP)> MyClientHandler -(channel)> Hub
+            client_msg = messages.recv() => {
+                match client_msg {
+                    Some(Ok(client_msg)) => {
+                        last_seen = Instant::now();
 
                         match client_msg {
-                            Message::Attacked => {
-                                opponent_writer.send(client_msg).await
-                                    .expect("Opponent channel should be opened");
+                            // 하트비트는 허브에게 전달하지 않는다
+                            Message::Ping { sent_at } => {
+                                let result = write_message(&mut writer, &Message::Pong { sent_at }).await;
+                                if result.is_err() {
+                                    lost = Some("disconnected");
+                                }
                             }
-                            Message::GameOver => {
-                                opponent_writer.send(client_msg).await
-                                    .expect("Opponent channel should be opened");
-
-                                break;  // 클라이언트 핸들러 종료
+                            Message::Attacked { .. } | Message::Target { .. } | Message::MiniBoard { .. } | Message::State { .. } | Message::Board { .. } | Message::GameOver { .. } | Message::Victory { .. } => {
+                                // 상태와 화면은 자주 바뀌므로 로그에 남기지 않는다
+                                if !matches!(client_msg, Message::State { .. } | Message::Board { .. }) {
+                                    println!("[Server] [Client{} Handler] RELAY message from Client{} to hub: {:?}", client_no, client_no, client_msg);
+                                }
+                                if hub_writer.send((index, Event::Client(client_msg))).await.is_err() {
+                                    break;  // 매치 종료됨
+                                }
                             }
                             _ => {} // 위 메세지 타입 외에는 무시
                         }
                     }
-                    Err(e) => {
-                        println!("[Server] [Client{} Handler] GOT read error from CLIENT{}: {:?}", client_no, client_no, e);
-                        break;  // 클라이언트 핸들러 종료
+                    // 읽지 못한 메세지 하나는 건너뛴다
+                    Some(Err(e)) if e.is_recoverable() => {
+                        println!("[Server] [Client{} Handler] Skipped message from Client{}: {}", client_no, client_no, e);
+                    }
+                    Some(Err(e)) => {
+                        println!("[Server] [Client{} Handler] GOT read error from CLIENT{}: {}", client_no, client_no, e);
+                        lost = Some("disconnected");
+                    }
+                    None => {
+                        println!("[Server] [Client{} Handler] Client{} reader closed", client_no, client_no);
+                        lost = Some("disconnected");
                     }
                 }
             }
 
-            // 상대 클라이언트에서 발생한 메세지를 내 클라이언트에게 전달
-            // OpponentClientHandler -(channel)> MyClientHandler -(TCP)> MyClient
+            // 주기적으로 Ping을 보내고, 응답이 없는 클라이언트는 끊긴 것으로 본다
+            _ = ping.tick() => {
+                if last_seen.elapsed() > heartbeat.timeout {
+                    println!("[Server] [Client{} Handler] Client{} timed out", client_no, client_no);
+                    lost = Some("timed out");
+                    continue;
+                }
+                let sent_at = chrono::Utc::now().timestamp_millis();
+                if write_message(&mut writer, &Message::Ping { sent_at }).await.is_err() {
+                    lost = Some("disconnected");
+                }
+            }
+
+            // 연결이 끊긴 걸 알아채기 전에 클라이언트가 다시 접속한 경우 새 연결로 바꾼다
+            Some(socket) = reconnects.recv() => {
+                let (reader, new_writer) = socket.into_split();
+                messages = spawn_message_reader(reader);
+                writer = new_writer;
+                last_seen = Instant::now();
+                println!("[Server] [Client{} Handler] Client{} switched to a new connection", client_no, client_no);
+                if write_message(&mut writer, &Message::Resumed).await.is_err() {
+                    lost = Some("disconnected");
+                }
+            }
+
+            // 허브에서 온 메세지를 내 클라이언트에게 전달
+            // Hub -(channel)> MyClientHandler -(TCP)> MyClient
             msg = my_reader.recv() => {
                 match msg {
                     Some(msg) => {
-                        println!("[Server] [Client{} Handler] GOT message from opponent client handler: {:?}", client_no, msg);
-                        match msg {
-                            Message::Attacked => {
-                                socket.write_all(&[msg as u8]).await
-                                    .expect("Opponent TcpStream should be opened");
-                            }
-                            Message::GameOver => {
-                                socket.write_all(&[msg as u8]).await
-                                    .expect("Opponent TcpStream should be opened");
-                                break;  // 클라이언트 핸들러 종료
+                        if !matches!(msg, Message::PlayerState { .. } | Message::PlayerBoard { .. }) {
+                            println!("[Server] [Client{} Handler] GOT message from hub: {:?}", client_no, msg);
+                        }
+                        let result = write_message(&mut writer, &msg).await;
+                        if let Message::MatchOver { .. } = msg {
+                            if let Err(e) = result {
+                                println!("[Server] [Client{} Handler] Failed to send result to Client{}: {}", client_no, client_no, e);
                             }
-                            _ => {} // 위 메세지 타입 외에는 무시
+                            break;  // 클라이언트 핸들러 종료
+                        }
+                        if result.is_err() {
+                            lost = Some("disconnected");
                         }
                     }
                     None => {
-                        println!("[Server] [Client{} Handler] Opponent client handler channel closed", client_no);
+                        println!("[Server] [Client{} Handler] Hub channel closed", client_no);
                         break;  // 클라이언트 핸들러 종료
                     }
                 }
@@ -182,10 +1477,92 @@ async fn handler(
     // tokio channel clean shutdown
     // see https://docs.rs/tokio/latest/tokio/sync/mpsc/index.html#clean-shutdown
     my_reader.close();
-    while let Some(_) = my_reader.recv().await {}
+    while my_reader.recv().await.is_some() {}
 
-    // TcpStream shutdown
-    socket.shutdown().await.unwrap();
+    // TcpStream shutdown (이미 끊긴 경우 무시)
+    _ = writer.shutdown().await;
 
     println!("[Server] [Client{} Handler] Closed", client_no);
 }
+
+enum Away {
+    Resumed(TcpStream),
+    Expired,
+    Ended,
+}
+
+// 유예 시간 동안 같은 세션 토큰으로 재접속하기를 기다린다
+// 기다리는 동안 허브에서 온 메세지는 모아 두었다가 재접속하면 보낸다
+async fn wait_for_reconnect(
+    reconnects: &mut Receiver<TcpStream>,
+    my_reader: &mut Receiver<Message>,
+    grace: Duration,
+    missed: &mut Vec<Message>,
+) -> Away {
+    let deadline = sleep(grace);
+    tokio::pin!(deadline);
+    loop {
+        tokio::select! {
+            _ = &mut deadline => return Away::Expired,
+            socket = reconnects.recv() => {
+                return match socket {
+                    Some(socket) => Away::Resumed(socket),
+                    None => Away::Expired,
+                };
+            }
+            msg = my_reader.recv() => {
+                match msg {
+                    Some(Message::MatchOver { .. }) | None => return Away::Ended,
+                    Some(msg) => missed.push(msg),
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn waited(secs: u64) -> Instant {
+        Instant::now() - Duration::from_secs(secs)
+    }
+
+    #[test]
+    fn rating_range_widens_while_waiting() {
+        assert_eq!(rating_range(Instant::now()), RATING_RANGE);
+        assert_eq!(
+            rating_range(waited(5)),
+            RATING_RANGE + 5 * RATING_RANGE_PER_SEC
+        );
+    }
+
+    #[test]
+    fn find_pair_picks_closest_rating() {
+        let now = Instant::now();
+        let waiting = [(1000, now), (1090, now), (1020, now)];
+        assert_eq!(find_pair(&waiting), Some((0, 2)));
+    }
+
+    #[test]
+    fn find_pair_waits_until_range_covers_gap() {
+        assert_eq!(
+            find_pair(&[(1000, Instant::now()), (1300, Instant::now())]),
+            None
+        );
+        // 한쪽이 오래 기다렸으면 넓어진 범위로 짝을 짓는다
+        assert_eq!(
+            find_pair(&[(1000, waited(10)), (1300, Instant::now())]),
+            Some((0, 1))
+        );
+    }
+
+    #[test]
+    fn rating_stakes_follow_elo() {
+        assert_eq!(rating_stakes(1000, 1000), (16, -16));
+        let (win, loss) = rating_stakes(1400, 1000);
+        assert!(win < 16 && loss < -16);
+        let (win, loss) = rating_stakes(1000, 1400);
+        assert!(win > 16 && loss > -16);
+    }
+}
@@ -1,5 +1,156 @@
-use raingame::play;
+use clap::{Parser, ValueEnum};
+
+use raingame::{
+    play_with, CodeGenerator, GameMode, MarkovGenerator, MathDifficulty, MathGenerator,
+    PassageGenerator, SentenceGenerator, Timeline, VocabGenerator, WordSource,
+};
+
+// 문장 조각의 최대 길이 (화면 가로 길이의 절반보다 조금 짧게)
+const SENTENCE_MAX_LEN: usize = 32;
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum Source {
+    // Words from the vocab pack
+    Vocab,
+    // Pronounceable nonsense words generated from the vocab
+    Pseudo,
+    // Fragments of several vocab words
+    Sentence,
+    // Arithmetic expressions; type the result
+    Math,
+    // Programming tokens, matched case- and punctuation-sensitively
+    Code,
+    // Words of a text file in reading order
+    Book,
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum Difficulty {
+    Easy,
+    Normal,
+    Hard,
+}
+
+#[derive(Parser, Debug)]
+struct Opts {
+    // Where falling words come from
+    #[arg(short, long, value_enum, default_value_t = Source::Vocab)]
+    source: Source,
+
+    // Difficulty of math problems
+    #[arg(short, long, value_enum, default_value_t = Difficulty::Normal)]
+    difficulty: Difficulty,
+
+    // Vocab pack used by the vocab, pseudo and sentence sources
+    #[arg(long, default_value = "./config/vocab.txt")]
+    vocab: String,
+
+    // Token list used by the code source
+    #[arg(long, default_value = "./config/code.txt")]
+    code_file: String,
+
+    // Text file used by the book source
+    #[arg(long, default_value = "./book.txt")]
+    book: String,
+
+    // Start the book from the beginning instead of the saved position
+    #[arg(long)]
+    restart: bool,
+
+    // Custom game mode in ./config/modes/<name>.toml
+    #[arg(short, long)]
+    mode: Option<String>,
+
+    // Spawn words at the times written in this timeline file instead of every 2 seconds
+    #[arg(short, long)]
+    timeline: Option<String>,
+
+    // Markov chain order used for pseudo words
+    #[arg(long, default_value_t = 2)]
+    order: usize,
+
+    // Minimum length of pseudo words
+    #[arg(long, default_value_t = 3)]
+    min_len: usize,
+
+    // Maximum length of pseudo words
+    #[arg(long, default_value_t = 8)]
+    max_len: usize,
+
+    // Minimum number of words in a sentence fragment
+    #[arg(long, default_value_t = 2)]
+    min_words: usize,
+
+    // Maximum number of words in a sentence fragment
+    #[arg(long, default_value_t = 4)]
+    max_words: usize,
+}
 
 fn main() {
-    play();
-}
\ No newline at end of file
+    let opts = Opts::parse();
+
+    // 단어 팩은 단어를 쓰는 소스에서만 읽는다
+    let vocab = || match VocabGenerator::from_file(&opts.vocab) {
+        Ok(vocab) => vocab,
+        Err(e) => {
+            eprintln!("[Single] {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let word_source: Box<dyn WordSource> = match opts.source {
+        Source::Vocab => Box::new(vocab()),
+        Source::Pseudo => Box::new(MarkovGenerator::from_vocab(
+            &vocab(),
+            opts.order,
+            opts.min_len,
+            opts.max_len,
+        )),
+        Source::Sentence => Box::new(SentenceGenerator::new(
+            vocab(),
+            opts.min_words,
+            opts.max_words,
+            SENTENCE_MAX_LEN,
+        )),
+        Source::Math => Box::new(MathGenerator::new(match opts.difficulty {
+            Difficulty::Easy => MathDifficulty::Easy,
+            Difficulty::Normal => MathDifficulty::Normal,
+            Difficulty::Hard => MathDifficulty::Hard,
+        })),
+        Source::Code => match CodeGenerator::new(&opts.code_file) {
+            Ok(code) => Box::new(code),
+            Err(e) => {
+                eprintln!("[Single] {}", e);
+                std::process::exit(1);
+            }
+        },
+        Source::Book => match PassageGenerator::new(&opts.book, !opts.restart) {
+            Ok(passage) => Box::new(passage),
+            Err(e) => {
+                eprintln!("[Single] {}", e);
+                std::process::exit(1);
+            }
+        },
+    };
+
+    let timeline = opts.timeline.map(|path| match Timeline::from_file(&path) {
+        Ok(timeline) => timeline,
+        Err(e) => {
+            eprintln!("[Single] {}", e);
+            std::process::exit(1);
+        }
+    });
+
+    let mode = match opts.mode {
+        Some(name) => match GameMode::load(&name) {
+            Ok(mode) => mode,
+            Err(e) => {
+                eprintln!("[Single] {}", e);
+                std::process::exit(1);
+            }
+        },
+        None => GameMode::default(),
+    };
+
+    play_with(word_source, timeline, mode);
+}
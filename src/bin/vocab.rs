@@ -0,0 +1,299 @@
+use clap::{Parser, Subcommand, ValueEnum};
+
+use raingame::VocabEntry;
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::process::ExitCode;
+
+use unicode_normalization::UnicodeNormalization;
+use unicode_width::UnicodeWidthStr;
+
+// 게임 화면의 가로 길이 (client/single)
+const BOARD_WIDTH: usize = 80;
+
+#[derive(Parser, Debug)]
+struct Opts {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    // Check a vocab pack and report every problem line
+    Lint {
+        path: String,
+
+        // Width of the game board
+        #[arg(short, long, default_value_t = BOARD_WIDTH)]
+        width: usize,
+    },
+
+    // Rewrite a vocab pack: NFC-normalize, trim, drop blank/duplicate/broken lines
+    Fix {
+        path: String,
+
+        // Output file (default: overwrite the input)
+        #[arg(short, long)]
+        output: Option<String>,
+
+        // Width of the game board
+        #[arg(short, long, default_value_t = BOARD_WIDTH)]
+        width: usize,
+    },
+
+    // Build a vocab pack from a frequency list or a text corpus
+    Convert {
+        path: String,
+
+        // Input format
+        #[arg(short, long, value_enum, default_value_t = Format::Freq)]
+        from: Format,
+
+        // Output file (default: stdout)
+        #[arg(short, long)]
+        output: Option<String>,
+
+        // Minimum word length
+        #[arg(long, default_value_t = 2)]
+        min_len: usize,
+
+        // Maximum word length
+        #[arg(long, default_value_t = 20)]
+        max_len: usize,
+
+        // Minimum count to keep a word
+        #[arg(long, default_value_t = 1)]
+        min_count: u64,
+
+        // Keep only the N most frequent words
+        #[arg(long)]
+        limit: Option<usize>,
+    },
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum Format {
+    // `word<TAB>count` per line
+    Freq,
+    // Arbitrary plain text
+    Text,
+}
+
+fn main() -> ExitCode {
+    let opts = Opts::parse();
+
+    let result = match opts.command {
+        Command::Lint { path, width } => lint(&path, width),
+        Command::Fix {
+            path,
+            output,
+            width,
+        } => fix(&path, output.as_deref().unwrap_or(&path), width),
+        Command::Convert {
+            path,
+            from,
+            output,
+            min_len,
+            max_len,
+            min_count,
+            limit,
+        } => convert(
+            &path,
+            from,
+            output.as_deref(),
+            (min_len, max_len),
+            min_count,
+            limit,
+        ),
+    };
+
+    match result {
+        Ok(code) => code,
+        Err(e) => {
+            eprintln!("[Vocab] {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+// 한 줄에서 발견된 문제 목록
+fn check_line(line: &str, width: usize) -> Vec<String> {
+    let mut problems = Vec::new();
+
+    if line.trim().is_empty() {
+        problems.push("blank line".to_string());
+        return problems;
+    }
+    if line != line.trim_end() {
+        problems.push("trailing whitespace".to_string());
+    }
+    if line != line.trim_start() {
+        problems.push("leading whitespace".to_string());
+    }
+    if line.chars().any(|c| c.is_control() && c != '\t') {
+        problems.push("non-printable character".to_string());
+    }
+    if line.nfc().ne(line.chars()) {
+        problems.push("not NFC-normalized".to_string());
+    }
+    if line.matches('\t').count() > 1 {
+        problems.push("more than one tab".to_string());
+    }
+
+    if let Some(entry) = VocabEntry::parse(line) {
+        if entry.get_prompt().is_empty() || entry.get_answer().is_empty() {
+            problems.push("empty prompt or answer".to_string());
+        }
+        if entry.get_prompt().width() >= width {
+            problems.push(format!("longer than the board width ({})", width));
+        }
+    }
+
+    problems
+}
+
+fn lint(path: &str, width: usize) -> Result<ExitCode, String> {
+    let contents = fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?;
+
+    let mut seen = HashMap::<String, usize>::new();
+    let mut count = 0;
+    for (index, line) in contents.lines().enumerate() {
+        let line_no = index + 1;
+        let mut problems = check_line(line, width);
+
+        if let Some(entry) = VocabEntry::parse(line) {
+            let key = entry.get_answer().nfc().collect::<String>();
+            match seen.get(&key) {
+                Some(first) => problems.push(format!("duplicate of line {}", first)),
+                None => {
+                    seen.insert(key, line_no);
+                }
+            }
+        }
+
+        for problem in &problems {
+            println!("{}:{}: {}: {:?}", path, line_no, problem, line);
+        }
+        count += problems.len();
+    }
+
+    if count == 0 {
+        println!("{}: {} entries, no problems", path, seen.len());
+        Ok(ExitCode::SUCCESS)
+    } else {
+        println!("{}: {} problems", path, count);
+        Ok(ExitCode::FAILURE)
+    }
+}
+
+fn fix(path: &str, output: &str, width: usize) -> Result<ExitCode, String> {
+    let contents = fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?;
+
+    let mut seen = HashSet::<String>::new();
+    let mut lines = Vec::new();
+    let mut dropped = 0;
+    for line in contents.lines() {
+        let line = normalize(line);
+        if line.is_empty() || !check_line(&line, width).is_empty() {
+            dropped += 1;
+            continue;
+        }
+        let answer = match VocabEntry::parse(&line) {
+            Some(entry) => entry.get_answer().clone(),
+            None => continue,
+        };
+        if seen.insert(answer) {
+            lines.push(line);
+        } else {
+            dropped += 1;
+        }
+    }
+
+    write_lines(Some(output), &lines)?;
+    eprintln!(
+        "[Vocab] {} entries written to {}, {} lines dropped",
+        lines.len(),
+        output,
+        dropped
+    );
+    Ok(ExitCode::SUCCESS)
+}
+
+fn convert(
+    path: &str,
+    from: Format,
+    output: Option<&str>,
+    (min_len, max_len): (usize, usize),
+    min_count: u64,
+    limit: Option<usize>,
+) -> Result<ExitCode, String> {
+    let contents = fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?;
+
+    let mut counts = HashMap::<String, u64>::new();
+    match from {
+        Format::Freq => {
+            for line in contents.lines() {
+                let mut fields = line.split(['\t', ' ', ',']).filter(|f| !f.is_empty());
+                let word = match fields.next() {
+                    Some(word) => normalize(word),
+                    None => continue,
+                };
+                let count = fields.next().and_then(|c| c.parse().ok()).unwrap_or(1);
+                *counts.entry(word).or_insert(0) += count;
+            }
+        }
+        Format::Text => {
+            let text = contents.nfc().collect::<String>();
+            for token in text.split(|c: char| !(c.is_alphanumeric() || c == '\'')) {
+                let word = token.trim_matches('\'').to_lowercase();
+                if word.is_empty() || word.chars().any(|c| c.is_numeric()) {
+                    continue;
+                }
+                *counts.entry(word).or_insert(0) += 1;
+            }
+        }
+    }
+
+    let mut words = counts
+        .into_iter()
+        .filter(|(word, count)| {
+            let len = word.chars().count();
+            *count >= min_count && min_len <= len && len <= max_len && word.width() < BOARD_WIDTH
+        })
+        .collect::<Vec<_>>();
+    // 빈도 내림차순, 같은 빈도는 사전순
+    words.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    if let Some(limit) = limit {
+        words.truncate(limit);
+    }
+
+    let lines = words.into_iter().map(|(word, _)| word).collect::<Vec<_>>();
+    write_lines(output, &lines)?;
+    eprintln!("[Vocab] {} words converted", lines.len());
+    Ok(ExitCode::SUCCESS)
+}
+
+// NFC 정규화 + 공백/제어문자 정리
+fn normalize(line: &str) -> String {
+    let line = line
+        .nfc()
+        .filter(|c| !c.is_control() || *c == '\t')
+        .collect::<String>();
+    match line.split_once('\t') {
+        Some((prompt, answer)) => format!("{}\t{}", prompt.trim(), answer.trim()),
+        None => line.trim().to_string(),
+    }
+}
+
+fn write_lines(output: Option<&str>, lines: &[String]) -> Result<(), String> {
+    let mut contents = lines.join("\n");
+    contents.push('\n');
+    match output {
+        Some(path) => fs::write(path, contents).map_err(|e| format!("{}: {}", path, e)),
+        None => {
+            print!("{}", contents);
+            Ok(())
+        }
+    }
+}
@@ -1,4 +1,6 @@
-#[derive(Clone, Copy)]
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
 pub enum WordColor {
     White = 0,
     Black = 1,
@@ -17,22 +19,298 @@ pub enum GameState {
     Win = 5,
 }
 
-#[derive(Debug)]
+// 공격 종류
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum AttackKind {
+    Rain,     // 빨간 단어 추가
+    SpeedUp,  // 단어가 빨리 떨어짐
+    Blind,    // 입력 줄 숨김
+    Scramble, // 화면의 단어 글자 섞기
+    Fog,      // 화면 아래쪽 절반 가림
+}
+
+impl AttackKind {
+    pub const ALL: [AttackKind; 5] = [
+        AttackKind::Rain,
+        AttackKind::SpeedUp,
+        AttackKind::Blind,
+        AttackKind::Scramble,
+        AttackKind::Fog,
+    ];
+
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            AttackKind::Rain => "RAIN",
+            AttackKind::SpeedUp => "SPEED",
+            AttackKind::Blind => "BLIND",
+            AttackKind::Scramble => "SCRAMBLE",
+            AttackKind::Fog => "FOG",
+        }
+    }
+}
+
+// 공격을 받을 상대 (여러 명이 하는 매치)
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum Target {
+    Random,
+    LowestScore,
+    Player(usize), // GameStart의 players 순서
+}
+
+impl Target {
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            Target::Random => "RANDOM",
+            Target::LowestScore => "LOWEST",
+            Target::Player(_) => "PLAYER",
+        }
+    }
+}
+
+// 방 목록에 보이는 방 하나
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RoomInfo {
+    pub code: String,
+    pub host: String,
+    // 비밀번호가 걸린 방
+    pub locked: bool,
+    // 지금 들어온 인원과 정원
+    pub players: usize,
+    pub size: usize,
+    // 팀 수 (0이면 개인전)
+    pub teams: usize,
+    // 이미 시작한 매치 (관전만 가능)
+    pub playing: bool,
+}
+
+// 화면에 보이는 단어 하나 (관전용)
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BoardWord {
+    pub x: i32,
+    pub y: i32,
+    pub text: String,
+    pub color: WordColor,
+}
+
+// 플레이어 화면 (관전용)
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Board {
+    pub words: Vec<BoardWord>,
+    pub input: String,
+    pub score: i32,
+    pub life: i32,
+}
+
+// 매치 시작 때의 레이팅과 결과에 따른 변화량
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Rating {
+    pub before: i32,
+    // 상대 레이팅 (여러 명이면 평균, 팀전이면 상대 팀 평균)
+    pub opponent: i32,
+    pub on_win: i32,
+    pub on_loss: i32,
+}
+
+// 서버와 클라이언트가 주고받는 메세지 (길이 + JSON으로 전송)
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub enum Message {
-    Waiting = 0,
-    GameStart = 1,
-    GameOver = 2,
-    Attacked = 3,
-}
-
-impl From<u8> for Message {
-    fn from(byte: u8) -> Self {
-        match byte {
-            0 => Message::Waiting,
-            1 => Message::GameStart,
-            2 => Message::GameOver,
-            3 => Message::Attacked,
-            _ => panic!("unexpected message"),
+    // 핸드셰이크
+    // 클라이언트 -> 서버: 매직 넘버, 프로토콜 버전, 플레이어 이름
+    // 재접속할 때는 게임 시작 때 받은 세션 토큰을 함께 보낸다
+    Hello {
+        magic: String,
+        version: u16,
+        name: String,
+        token: Option<String>,
+    },
+    // 서버 -> 클라이언트: 접속 허용
+    Welcome {
+        version: u16,
+    },
+    // 서버 -> 클라이언트: 접속 거부 사유
+    Reject {
+        reason: String,
+    },
+    // 서버 -> 클라이언트: vocab 해시
+    VocabHash {
+        digest: String,
+    },
+    // 클라이언트 -> 서버: 캐시에 없으면 true
+    VocabRequest {
+        send: bool,
+    },
+    // 서버 -> 클라이언트: vocab 내용
+    Vocab {
+        contents: String,
+    },
+    // 서버 -> 클라이언트: 게임 모드 (비어 있으면 기본 게임)
+    Mode {
+        contents: String,
+    },
+
+    // 로비: 클라이언트 -> 서버
+    // 아무 상대와 매치
+    QuickMatch,
+    // 방 만들기 (private이면 목록에 보이지 않음, password가 있으면 입장할 때 필요)
+    // size명이 모이면 게임 시작, teams가 0이 아니면 들어온 순서대로 팀을 나눈다
+    CreateRoom {
+        private: bool,
+        password: Option<String>,
+        size: usize,
+        teams: usize,
+    },
+    // 참가 코드로 방 입장
+    JoinRoom {
+        code: String,
+        password: Option<String>,
+    },
+    ListRooms,
+    // 진행 중인 매치 관전 (비밀번호가 걸린 방이면 password 필요)
+    Watch {
+        code: String,
+        password: Option<String>,
+    },
+
+    // 로비: 서버 -> 클라이언트
+    RoomCreated {
+        code: String,
+    },
+    Rooms {
+        rooms: Vec<RoomInfo>,
+    },
+
+    // 게임
+    Waiting,
+    // 매치의 모든 플레이어 이름과 팀(개인전이면 플레이어마다 다른 팀), 그중 내 순서,
+    // 세션 토큰, 재접속 유예 시간(초)과 레이팅
+    GameStart {
+        players: Vec<String>,
+        teams: Vec<usize>,
+        you: usize,
+        token: String,
+        grace: f32,
+        rating: Rating,
+    },
+    // 공격 단어, 종류와 세기 (빨간 단어 수 또는 디버프 지속 시간)
+    Attacked {
+        word: String,
+        kind: AttackKind,
+        strength: u32,
+    },
+    // 클라이언트 -> 서버: 공격 대상 변경
+    Target {
+        target: Target,
+    },
+    // 클라이언트 -> 서버: 미니 보드에 쓸 상대 화면 받기 (기본은 받지 않음)
+    MiniBoard {
+        enabled: bool,
+    },
+    // 클라이언트 -> 서버: 점수, 목숨, 콤보, 받을 공격 (바뀔 때와 주기적으로 전송)
+    State {
+        score: i32,
+        life: i32,
+        combo: i32,
+        pending: u32,
+    },
+    // 클라이언트 -> 서버: 내 게임 패배 (탈락 후 관전)
+    GameOver {
+        score: i32,
+    },
+    // 클라이언트 -> 서버: 게임 모드의 승리 조건 달성
+    Victory {
+        score: i32,
+    },
+
+    // 서버 -> 클라이언트: 다른 플레이어의 점수, 목숨, 콤보, 받을 공격
+    PlayerState {
+        player: usize,
+        score: i32,
+        life: i32,
+        combo: i32,
+        pending: u32,
+    },
+    // 서버 -> 클라이언트: 플레이어 탈락 (패배 또는 접속 끊김)
+    PlayerOut {
+        player: usize,
+        score: i32,
+        reason: String,
+    },
+    // 클라이언트 -> 서버: 관전자와 다른 플레이어에게 보여줄 내 화면 (주기적으로 전송)
+    Board {
+        board: Board,
+    },
+    // 서버 -> 관전자: 관전 시작, 매치의 플레이어 이름과 팀
+    Spectate {
+        players: Vec<String>,
+        teams: Vec<usize>,
+    },
+    // 서버 -> 관전자, 클라이언트: 플레이어 화면
+    PlayerBoard {
+        player: usize,
+        board: Board,
+    },
+    // 서버 -> 클라이언트: 매치 종료, 이긴 팀 (승자가 없으면 None)
+    MatchOver {
+        winner: Option<usize>,
+    },
+
+    // 플레이어가 잠시 끊김 (grace초 안에 돌아오지 않으면 탈락)
+    PlayerAway {
+        player: usize,
+        grace: f32,
+    },
+    PlayerBack {
+        player: usize,
+    },
+    // 서버 -> 재접속한 클라이언트: 같은 게임을 이어서 진행
+    Resumed,
+
+    // 하트비트: 받은 쪽은 sent_at(보낸 쪽 시각, ms)을 그대로 Pong으로 돌려준다
+    Ping {
+        sent_at: i64,
+    },
+    Pong {
+        sent_at: i64,
+    },
+}
+
+impl Message {
+    // 로그용 메세지 종류 이름 (vocab 내용 등 본문은 찍지 않는다)
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Message::Hello { .. } => "Hello",
+            Message::Welcome { .. } => "Welcome",
+            Message::Reject { .. } => "Reject",
+            Message::VocabHash { .. } => "VocabHash",
+            Message::VocabRequest { .. } => "VocabRequest",
+            Message::Vocab { .. } => "Vocab",
+            Message::Mode { .. } => "Mode",
+            Message::QuickMatch => "QuickMatch",
+            Message::CreateRoom { .. } => "CreateRoom",
+            Message::JoinRoom { .. } => "JoinRoom",
+            Message::ListRooms => "ListRooms",
+            Message::Watch { .. } => "Watch",
+            Message::RoomCreated { .. } => "RoomCreated",
+            Message::Rooms { .. } => "Rooms",
+            Message::Waiting => "Waiting",
+            Message::GameStart { .. } => "GameStart",
+            Message::Attacked { .. } => "Attacked",
+            Message::Target { .. } => "Target",
+            Message::MiniBoard { .. } => "MiniBoard",
+            Message::State { .. } => "State",
+            Message::GameOver { .. } => "GameOver",
+            Message::Victory { .. } => "Victory",
+            Message::PlayerState { .. } => "PlayerState",
+            Message::PlayerOut { .. } => "PlayerOut",
+            Message::Board { .. } => "Board",
+            Message::Spectate { .. } => "Spectate",
+            Message::PlayerBoard { .. } => "PlayerBoard",
+            Message::MatchOver { .. } => "MatchOver",
+            Message::PlayerAway { .. } => "PlayerAway",
+            Message::PlayerBack { .. } => "PlayerBack",
+            Message::Resumed => "Resumed",
+            Message::Ping { .. } => "Ping",
+            Message::Pong { .. } => "Pong",
         }
     }
 }
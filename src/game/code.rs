@@ -0,0 +1,26 @@
+use super::source::WordSource;
+use super::vocab::{VocabEntry, VocabGenerator};
+
+// 프로그래밍 토큰 연습용 단어 생성기
+// 대소문자와 기호를 정확히 입력해야 한다
+pub struct CodeGenerator {
+    vocab_generator: VocabGenerator,
+}
+
+impl CodeGenerator {
+    pub fn new(path: &str) -> Result<Self, String> {
+        Ok(CodeGenerator {
+            vocab_generator: VocabGenerator::from_file(path)?,
+        })
+    }
+}
+
+impl WordSource for CodeGenerator {
+    fn generate(&mut self) -> VocabEntry {
+        self.vocab_generator.generate()
+    }
+
+    fn is_strict(&self) -> bool {
+        true
+    }
+}
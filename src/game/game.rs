@@ -2,16 +2,42 @@ use std::collections::VecDeque;
 use std::time::{Duration, Instant};
 
 use rand::Rng;
+use unicode_width::UnicodeWidthStr;
 
 use ncurses::*;
 
-use super::vocab::VocabGenerator;
+use super::mode::GameMode;
+use super::source::WordSource;
+use super::timeline::{Timeline, TimelineEvent};
+use super::vocab::{VocabEntry, VocabGenerator};
 use super::word::Word;
-use crate::{GameState, WordColor};
+use crate::{AttackKind, Board, BoardWord, GameState, WordColor};
 
 const WIDTH: i32 = 80;
 const HEIGHT: i32 = 24;
 
+// 직전 단어와 겹치지 않는 자리를 다시 뽑는 횟수
+const SPAWN_RETRIES: usize = 8;
+
+// 놓친 단어의 정답을 보여주는 시간
+const REVEAL_DURATION: Duration = Duration::from_millis(1500);
+
+// 공격 세기: 공격 단어 글자 수 / ATTACK_CHARS_PER_WORD + 콤보 / COMBO_PER_STRENGTH
+const ATTACK_CHARS_PER_WORD: usize = 4;
+const COMBO_PER_STRENGTH: i32 = 5;
+// 서버와 받는 쪽도 이 값으로 자르므로 조작된 클라이언트가 보낸 세기도 넘지 못한다
+pub const MAX_ATTACK_STRENGTH: u32 = 8;
+
+// 디버프 지속 시간: DEBUFF_BASE + 세기 * DEBUFF_PER_STRENGTH
+const DEBUFF_BASE: Duration = Duration::from_secs(3);
+const DEBUFF_PER_STRENGTH: Duration = Duration::from_secs(1);
+
+// 받은 공격이 실제로 적용되기까지의 시간 (그 사이에 공격 단어로 상쇄할 수 있다)
+const ATTACK_DELAY: Duration = Duration::from_secs(3);
+
+// SpeedUp 디버프 동안 떨어지는 속도 배율
+const SPEED_UP_FACTOR: f32 = 2.0;
+
 pub struct Game {
     score: i32,
     words: VecDeque<Word>,
@@ -19,42 +45,115 @@ pub struct Game {
     speed_factor: f32,
     height: i32,
     width: i32,
-    vocab_generator: VocabGenerator,
+    word_source: Box<dyn WordSource>,
     input_string: String,
     life: i32,
     game_state: GameState,
-    attack_string: String,
+    attack_vocab: VocabEntry,
     latest_spawned_word: Word,
+    revealed_word: Option<(Word, Instant)>,
+    timeline: Option<Timeline>,
+    started_at: Instant,
+    mode: GameMode,
+    wave_spawns: VecDeque<(Duration, Option<f32>, WordColor)>,
+    combo: i32,
+    attack_kind: AttackKind,
+    last_attack: (AttackKind, u32),
+    debuffs: Vec<(AttackKind, Instant)>,
+    pending_attacks: VecDeque<(AttackKind, u32, Instant)>,
 }
 
 impl Game {
     pub fn new(height: i32, width: i32) -> Self {
+        Game::with_source(height, width, Box::new(VocabGenerator::new()))
+    }
+
+    pub fn with_source(height: i32, width: i32, word_source: Box<dyn WordSource>) -> Self {
+        let mode = GameMode::default();
         Game {
             score: 0,
             words: VecDeque::new(),
             last_spawn_time: Instant::now(),
-            speed_factor: 0.0,
-            height: height,
-            width: width,
-            vocab_generator: VocabGenerator::new(),
+            speed_factor: mode.rules.speed.speed(0),
+            height,
+            width,
+            word_source,
             input_string: String::new(),
-            life: 5,
+            life: mode.rules.lives,
             game_state: GameState::StartGame,
-            attack_string: String::new(),
+            attack_vocab: VocabEntry::default(),
             latest_spawned_word: Word::new(0.0, 0.0, String::new(), crate::WordColor::White),
+            revealed_word: None,
+            timeline: None,
+            started_at: Instant::now(),
+            mode,
+            wave_spawns: VecDeque::new(),
+            combo: 0,
+            attack_kind: AttackKind::Rain,
+            last_attack: (AttackKind::Rain, 0),
+            debuffs: Vec::new(),
+            pending_attacks: VecDeque::new(),
         }
     }
 
+    // 게임 모드의 규칙과 웨이브를 적용한다
+    pub fn set_mode(&mut self, mode: GameMode) {
+        self.life = mode.rules.lives;
+        self.speed_factor = mode.rules.speed.speed(self.score);
+        self.wave_spawns = mode.wave_spawns().into();
+        self.mode = mode;
+        self.started_at = Instant::now();
+    }
+
+    // 고정된 2초 간격 대신 시간표에 따라 단어를 떨어뜨린다
+    pub fn set_timeline(&mut self, timeline: Timeline) {
+        self.timeline = Some(timeline);
+        self.started_at = Instant::now();
+    }
+
     pub fn update(&mut self) -> GameState {
-        if self.last_spawn_time.elapsed() > Duration::from_secs(2) {
-            self.spawn_word(WordColor::White);
-            self.last_spawn_time = Instant::now();
+        if let Some(timeline) = &mut self.timeline {
+            let events = timeline.pop_due(self.started_at.elapsed());
+            for event in events {
+                self.spawn_timeline_word(&event);
+            }
+        } else if let Some(spawn_interval) = self.mode.spawn_interval() {
+            if self.last_spawn_time.elapsed() > spawn_interval && !self.word_source.is_finished() {
+                self.spawn_word(WordColor::White);
+                self.last_spawn_time = Instant::now();
+            }
+        }
+
+        // 웨이브 단어 등장
+        let elapsed = self.started_at.elapsed();
+        while let Some((at, speed, color)) = self.wave_spawns.front().copied() {
+            if at > elapsed {
+                break;
+            }
+            self.wave_spawns.pop_front();
+            self.spawn_wave_word(color, speed);
         }
-        self.move_words();
 
         // 공격 단어 갱신
-        if self.attack_string.is_empty() {
-            self.attack_string = self.vocab_generator.generate();
+        if self.attack_vocab.get_answer().is_empty() {
+            self.refresh_attack();
+        }
+
+        // 대기 시간이 지난 공격 적용
+        while let Some((kind, strength, lands_at)) = self.pending_attacks.front().copied() {
+            if lands_at > Instant::now() {
+                break;
+            }
+            self.pending_attacks.pop_front();
+            self.apply_attack(kind, strength);
+        }
+
+        // 끝난 디버프 정리
+        self.debuffs.retain(|(_, until)| *until > Instant::now());
+        if !self.has_debuff(AttackKind::Scramble) {
+            for word in &mut self.words {
+                word.unscramble();
+            }
         }
 
         // if let Some(input_char) = input {
@@ -65,20 +164,51 @@ impl Game {
 
         // 각 단어 별로 Deadline을 넘었는지 판정
         let line_height = (self.height - 2) as f32;
+        let speed_multiplier = if self.has_debuff(AttackKind::SpeedUp) {
+            SPEED_UP_FACTOR
+        } else {
+            1.0
+        };
         for i in (0..self.words.len()).rev() {
             let word = &mut self.words[i];
-            word.set_y(word.get_y() + self.speed_factor);
+            word.set_y(
+                word.get_y() + word.get_speed().unwrap_or(self.speed_factor) * speed_multiplier,
+            );
 
             if word.get_y() >= line_height {
-                self.score -= word.get_text().len() as i32;
+                self.score -=
+                    word.get_answer().len() as i32 * self.mode.rules.scoring.miss_per_char;
+                if word.get_text() != word.get_answer() {
+                    // 학습 모드: 놓친 단어의 정답을 잠깐 보여준다
+                    let reveal_text = format!("{} = {}", word.get_text(), word.get_answer());
+                    let reveal_x = word
+                        .get_x()
+                        .min((self.width - reveal_text.width() as i32).max(0) as f32);
+                    self.revealed_word = Some((
+                        Word::new(reveal_x, line_height - 1.0, reveal_text, WordColor::Yellow),
+                        Instant::now(),
+                    ));
+                }
                 self.words.remove(i);
                 self.life -= 1;
+                self.combo = 0;
             }
         }
 
-        self.speed_factor = 0.1 + (self.score as f32) / 1000.0;
+        if let Some((_, revealed_at)) = &self.revealed_word {
+            if revealed_at.elapsed() > REVEAL_DURATION {
+                self.revealed_word = None;
+            }
+        }
+
+        self.speed_factor = self.mode.rules.speed.speed(self.score);
         self.game_state = if self.life <= 0 {
             GameState::Lose
+        } else if self.is_finished() && self.words.is_empty() {
+            // 본문 또는 시간표를 끝까지 입력함
+            GameState::Win
+        } else if self.is_won_by_rules() {
+            GameState::Win
         } else {
             GameState::InProgress
         };
@@ -87,37 +217,189 @@ impl Game {
     }
 
     pub fn spawn_word(&mut self, color: WordColor) {
+        let vocab = match color {
+            WordColor::White => self.word_source.generate(),
+            _ => self.word_source.generate_attack(),
+        };
+        if vocab.get_answer().is_empty() {
+            return;
+        }
+        self.spawn_vocab(vocab, color, None, None);
+    }
+
+    // 상대의 공격을 받는다
+    // 바로 적용하지 않고 ATTACK_DELAY 동안 대기열에 둔다
+    pub fn receive_attack(&mut self, kind: AttackKind, strength: u32) {
+        self.pending_attacks.push_back((
+            kind,
+            strength.clamp(1, MAX_ATTACK_STRENGTH),
+            Instant::now() + ATTACK_DELAY,
+        ));
+    }
+
+    // 대기 중인 공격의 세기 합과 다음 공격까지 남은 시간
+    pub fn get_pending_attack(&self) -> (u32, Option<Duration>) {
+        let total = self
+            .pending_attacks
+            .iter()
+            .map(|(_, strength, _)| strength)
+            .sum();
+        let next = self
+            .pending_attacks
+            .front()
+            .map(|(_, _, lands_at)| lands_at.saturating_duration_since(Instant::now()));
+        (total, next)
+    }
+
+    // 오래된 공격부터 세기를 깎고 남은 세기를 돌려준다
+    fn cancel_pending_attacks(&mut self, mut strength: u32) -> u32 {
+        while strength > 0 {
+            let front = match self.pending_attacks.front_mut() {
+                Some(front) => front,
+                None => break,
+            };
+            let cancelled = front.1.min(strength);
+            front.1 -= cancelled;
+            strength -= cancelled;
+            if front.1 == 0 {
+                self.pending_attacks.pop_front();
+            }
+        }
+        strength
+    }
+
+    // Rain은 세기만큼 빨간 단어를 떨어뜨리고, 나머지는 세기에 비례한 시간 동안 디버프를 건다
+    fn apply_attack(&mut self, kind: AttackKind, strength: u32) {
+        if kind == AttackKind::Rain {
+            for _ in 0..strength {
+                self.spawn_word(WordColor::Red);
+            }
+            return;
+        }
+
+        let until = Instant::now() + DEBUFF_BASE + DEBUFF_PER_STRENGTH * strength;
+        match self.debuffs.iter_mut().find(|(debuff, _)| *debuff == kind) {
+            Some((_, current)) => *current = (*current).max(until),
+            None => self.debuffs.push((kind, until)),
+        }
+        if kind == AttackKind::Scramble {
+            for word in &mut self.words {
+                word.scramble();
+            }
+        }
+    }
+
+    pub fn has_debuff(&self, kind: AttackKind) -> bool {
+        self.debuffs
+            .iter()
+            .any(|(debuff, until)| *debuff == kind && *until > Instant::now())
+    }
+
+    // 걸려 있는 디버프와 남은 시간
+    pub fn get_debuffs(&self) -> Vec<(AttackKind, Duration)> {
+        let now = Instant::now();
+        self.debuffs
+            .iter()
+            .filter(|(_, until)| *until > now)
+            .map(|(debuff, until)| (*debuff, *until - now))
+            .collect()
+    }
+
+    // 새 공격 단어와 공격 종류를 고른다
+    fn refresh_attack(&mut self) {
         let mut rng = rand::thread_rng();
-        let word_text = self.vocab_generator.generate();
-        let mut word_x = rng.gen_range(0.0, self.width as f32 - word_text.len() as f32) as f32;
+        self.attack_vocab = self.word_source.generate_attack();
+        self.attack_kind = AttackKind::ALL[rng.gen_range(0, AttackKind::ALL.len())];
+    }
+
+    fn spawn_wave_word(&mut self, color: WordColor, speed: Option<f32>) {
+        let vocab = match color {
+            WordColor::White => self.word_source.generate(),
+            _ => self.word_source.generate_attack(),
+        };
+        if vocab.get_answer().is_empty() {
+            return;
+        }
+        self.spawn_vocab(vocab, color, None, speed);
+    }
+
+    fn spawn_timeline_word(&mut self, event: &TimelineEvent) {
+        self.spawn_vocab(
+            event.get_vocab().clone(),
+            WordColor::White,
+            event.get_column(),
+            event.get_speed(),
+        );
+    }
+
+    fn spawn_vocab(
+        &mut self,
+        vocab: VocabEntry,
+        color: WordColor,
+        column: Option<i32>,
+        speed: Option<f32>,
+    ) {
+        let mut rng = rand::thread_rng();
+        let word_text = vocab.get_prompt();
+        // 한글은 한 글자가 두 칸을 차지하므로 화면에 보이는 너비로 잰다
+        let word_len = word_text.width() as f32;
+        let max_x = (self.width as f32 - word_len).max(1.0);
+        let mut word_x = rng.gen_range(0.0, max_x);
         let latest_x_min = self.latest_spawned_word.get_x() - 1.0;
         let latest_x_max = self.latest_spawned_word.get_x()
-            + self.latest_spawned_word.get_text().len() as f32
+            + self.latest_spawned_word.get_text().width() as f32
             + 1.0;
-        loop {
-            if (latest_x_min < word_x && word_x < latest_x_max)
-                || (latest_x_min < (word_x + word_text.len() as f32)
-                    && (word_x + word_text.len() as f32) < latest_x_max)
-            {
-                word_x = rng.gen_range(0.0, self.width as f32 - word_text.len() as f32) as f32;
-            } else {
-                break;
+        if let Some(column) = column {
+            word_x = (column as f32).clamp(0.0, max_x);
+        }
+        // 긴 단어가 이어지면 겹치지 않는 자리가 없을 수 있으므로 몇 번만 다시 뽑고 그대로 놓는다
+        let overlaps = |x: f32| {
+            (latest_x_min < x && x < latest_x_max)
+                || (latest_x_min < (x + word_len) && (x + word_len) < latest_x_max)
+        };
+        if column.is_none() {
+            for _ in 0..SPAWN_RETRIES {
+                if !overlaps(word_x) {
+                    break;
+                }
+                word_x = rng.gen_range(0.0, max_x);
             }
         }
         let word_y = 0.0;
-        self.words
-            .push_back(Word::new(word_x, word_y, word_text.clone(), color));
+        let mut word = Word::with_answer(
+            word_x,
+            word_y,
+            word_text.clone(),
+            vocab.get_answer().clone(),
+            color,
+        );
+        word.set_speed(speed);
+        self.words.push_back(word);
         self.latest_spawned_word = Word::new(word_x, word_y, word_text.clone(), WordColor::White);
     }
 
-    pub fn move_words(&mut self) {
-        for word in &mut self.words {
-            word.set_y(word.get_y() + 0.1);
-        }
-    }
-
     pub fn draw_words(&self) {
+        // Fog 디버프 동안 화면 아래쪽 절반의 단어는 보이지 않는다
+        let fog_y = if self.has_debuff(AttackKind::Fog) {
+            (self.height / 2) as f32
+        } else {
+            f32::MAX
+        };
         for word in &self.words {
+            if word.get_y() >= fog_y {
+                continue;
+            }
+            let color = word.get_color() as i16;
+            attron(COLOR_PAIR(color));
+            mvprintw(
+                word.get_y() as i32,
+                word.get_x() as i32,
+                word.get_display_text().as_str(),
+            );
+            attroff(COLOR_PAIR(color));
+        }
+
+        if let Some((word, _)) = &self.revealed_word {
             let color = word.get_color() as i16;
             attron(COLOR_PAIR(color));
             mvprintw(
@@ -129,6 +411,26 @@ impl Game {
         }
     }
 
+    // 관전자에게 보낼 화면 (디버프로 가려진 단어도 모두 보낸다)
+    pub fn get_board(&self) -> Board {
+        let words = self
+            .words
+            .iter()
+            .map(|word| BoardWord {
+                x: word.get_x() as i32,
+                y: word.get_y() as i32,
+                text: word.get_display_text().clone(),
+                color: word.get_color(),
+            })
+            .collect();
+        Board {
+            words,
+            input: self.input_string.clone(),
+            score: self.score,
+            life: self.life,
+        }
+    }
+
     pub fn get_score(&self) -> i32 {
         self.score
     }
@@ -142,7 +444,55 @@ impl Game {
     }
 
     pub fn get_attack_string(&self) -> String {
-        self.attack_string.clone()
+        self.attack_vocab.get_prompt().clone()
+    }
+
+    pub fn get_combo(&self) -> i32 {
+        self.combo
+    }
+
+    pub fn get_attack_kind(&self) -> AttackKind {
+        self.attack_kind
+    }
+
+    // 마지막으로 완성한 공격 단어의 종류와 세기
+    pub fn get_last_attack(&self) -> (AttackKind, u32) {
+        self.last_attack
+    }
+
+    fn calc_attack_strength(&self, answer: &str) -> u32 {
+        let strength = answer.chars().count() / ATTACK_CHARS_PER_WORD
+            + (self.combo / COMBO_PER_STRENGTH) as usize;
+        (strength as u32).clamp(1, MAX_ATTACK_STRENGTH)
+    }
+
+    pub fn get_progress(&self) -> Option<(usize, usize)> {
+        match &self.timeline {
+            Some(timeline) => Some(timeline.progress()),
+            None => self.word_source.progress(),
+        }
+    }
+
+    fn is_won_by_rules(&self) -> bool {
+        let win = &self.mode.rules.win;
+        if let Some(score) = win.score {
+            if self.score >= score {
+                return true;
+            }
+        }
+        if let Some(survive) = win.survive {
+            if self.started_at.elapsed().as_secs_f32() >= survive {
+                return true;
+            }
+        }
+        win.clear_waves && self.wave_spawns.is_empty() && self.words.is_empty()
+    }
+
+    fn is_finished(&self) -> bool {
+        match &self.timeline {
+            Some(timeline) => timeline.is_finished(),
+            None => self.word_source.is_finished(),
+        }
     }
 
     pub fn get_life(&self) -> i32 {
@@ -161,20 +511,84 @@ impl Game {
         self.input_string.pop();
     }
 
+    // 키 입력 처리
+    // 코드 모드(strict)에서는 `=`도 입력 문자로 취급한다
+    pub fn handle_input(&mut self, input: i32) -> GameState {
+        let strict = self.word_source.is_strict();
+        let input_char = if (0..=255).contains(&input) {
+            char::from_u32(input as u32)
+        } else {
+            None
+        };
+
+        match (input, input_char) {
+            (KEY_BACKSPACE, _)
+            | (KEY_DC, _)
+            | (127, _)
+            | (_, Some('\u{0008}'))
+            | (_, Some('\x7f')) => {
+                self.pop_input_string();
+            }
+            (_, Some('=')) if !strict => {
+                self.pop_input_string();
+            }
+            (KEY_ENTER, _) | (KEY_SEND, _) | (_, Some('\n')) => {
+                return self.enter_input_string();
+            }
+            (_, Some(c)) if !c.is_control() => {
+                self.push_input_string(c);
+            }
+            _ => {}
+        }
+        self.game_state
+    }
+
+    // strict 모드에서는 앞뒤 공백까지 정확히 일치해야 한다
+    fn matches_answer(&self, answer: &str) -> bool {
+        if self.word_source.is_strict() {
+            self.input_string == answer
+        } else {
+            self.input_string.trim() == answer
+        }
+    }
+
     pub fn enter_input_string(&mut self) -> GameState {
+        let mut matched = false;
         for i in (0..self.words.len()).rev() {
-            let word = &mut self.words[i];
-            if self.input_string.trim() == word.get_text().clone() {
-                self.score += word.get_text().len() as i32;
-                self.words.remove(i);
+            if self.matches_answer(self.words[i].get_answer()) {
+                self.score +=
+                    self.words[i].get_answer().len() as i32 * self.mode.rules.scoring.per_char;
+                if let Some(word) = self.words.remove(i) {
+                    self.word_source.complete(word.get_answer());
+                }
                 self.game_state = GameState::CompleteWord;
+                self.combo += 1;
+                matched = true;
                 break;
             }
         }
-        if self.input_string == self.attack_string {
-            self.score += self.attack_string.len() as i32;
-            self.attack_string = self.vocab_generator.generate();
-            self.game_state = GameState::CompleteAttackWord;
+        if self.matches_answer(self.attack_vocab.get_answer()) {
+            self.score += self.attack_vocab.get_answer().len() as i32
+                * self.mode.rules.scoring.attack_per_char;
+            // 떨어지는 단어와 공격 단어를 한 번에 맞혀도 콤보는 한 번만 오른다
+            if !matched {
+                self.combo += 1;
+            }
+            // 받을 공격이 대기 중이면 먼저 상쇄하고, 남은 세기만 상대에게 보낸다
+            let strength = self.calc_attack_strength(self.attack_vocab.get_answer());
+            let strength = self.cancel_pending_attacks(strength);
+            self.last_attack = (self.attack_kind, strength);
+            self.refresh_attack();
+            self.game_state = if strength > 0 {
+                GameState::CompleteAttackWord
+            } else {
+                GameState::CompleteWord
+            };
+            matched = true;
+        }
+        // 틀린 단어를 입력하면 콤보가 끊긴다
+        if !matched && !self.input_string.trim().is_empty() {
+            self.combo = 0;
         }
         self.input_string = String::new();
         self.game_state
@@ -182,41 +596,32 @@ impl Game {
 }
 
 pub fn play() {
+    play_with(Box::new(VocabGenerator::new()), None, GameMode::default());
+}
+
+pub fn play_with(word_source: Box<dyn WordSource>, timeline: Option<Timeline>, mode: GameMode) {
+    setlocale(LcCategory::all, "");
     initscr();
     cbreak();
     timeout(0);
     curs_set(CURSOR_VISIBILITY::CURSOR_INVISIBLE);
     keypad(stdscr(), true);
 
-    let mut game = Game::new(HEIGHT, WIDTH);
+    let mut game = Game::with_source(HEIGHT, WIDTH, word_source);
+    game.set_mode(mode);
+    if let Some(timeline) = timeline {
+        game.set_timeline(timeline);
+    }
     let line = "-".repeat(WIDTH as usize);
 
     loop {
         erase();
         let input = getch();
-        let input_char = if (input >= 0) && (input <= 255) {
-            char::from_u32(input as u32)
-        } else {
-            None
-        };
+        _ = game.handle_input(input);
 
-        if input_char.is_some() {
-            if input == KEY_BACKSPACE
-                || input == KEY_DC
-                || input == 127
-                || input_char.unwrap() == '\u{0008}'
-                || input_char.unwrap() == '='
-            {
-                game.pop_input_string();
-            }
-            if input == KEY_ENTER || input == KEY_SEND || input_char.unwrap() == '\n' {
-                _ = game.enter_input_string();
-            }
-        }
-
-        let game_state = game.update(); // game_state = InProgress or Lose
+        let game_state = game.update(); // game_state = InProgress, Lose or Win
 
-        if game_state == GameState::Lose {
+        if matches!(game_state, GameState::Lose | GameState::Win) {
             break;
         };
 
@@ -230,6 +635,10 @@ pub fn play() {
 
         mvprintw(0, WIDTH - life_string.len() as i32, &life_string);
         mvprintw(1, WIDTH - attack_string.len() as i32, &attack_string);
+        if let Some((position, total)) = game.get_progress() {
+            let progress_string = format!("PROGRESS: {}/{}", position, total);
+            mvprintw(2, WIDTH - progress_string.len() as i32, &progress_string);
+        }
         mvprintw(HEIGHT - 2, 0, &line);
         mvprintw(HEIGHT - 1, 0, input_prompt.as_str());
         refresh();
@@ -259,3 +668,44 @@ pub fn play() {
     }
     endwin();
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn game() -> Game {
+        Game::with_source(
+            20,
+            80,
+            Box::new(VocabGenerator::from_contents("rain").unwrap()),
+        )
+    }
+
+    #[test]
+    fn received_attacks_are_clamped() {
+        let mut game = game();
+        game.receive_attack(AttackKind::Rain, u32::MAX);
+        game.receive_attack(AttackKind::Rain, 0);
+        assert_eq!(game.get_pending_attack().0, MAX_ATTACK_STRENGTH + 1);
+    }
+
+    #[test]
+    fn cancel_takes_from_oldest_attack_first() {
+        let mut game = game();
+        game.receive_attack(AttackKind::Rain, 3);
+        game.receive_attack(AttackKind::Fog, 5);
+        assert_eq!(game.cancel_pending_attacks(4), 0);
+        assert_eq!(game.pending_attacks.len(), 1);
+        assert_eq!(game.pending_attacks[0].0, AttackKind::Fog);
+        assert_eq!(game.get_pending_attack().0, 4);
+    }
+
+    #[test]
+    fn cancel_returns_leftover_strength() {
+        let mut game = game();
+        game.receive_attack(AttackKind::Rain, 2);
+        assert_eq!(game.cancel_pending_attacks(5), 3);
+        assert_eq!(game.get_pending_attack(), (0, None));
+        assert_eq!(game.cancel_pending_attacks(2), 2);
+    }
+}
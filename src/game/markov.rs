@@ -0,0 +1,101 @@
+use rand::{thread_rng, Rng};
+use std::collections::{HashMap, HashSet};
+
+use super::source::WordSource;
+use super::vocab::{VocabEntry, VocabGenerator};
+
+// 단어의 시작과 끝을 나타내는 문자
+const START: char = '\u{2}';
+const END: char = '\u{3}';
+
+// 조건에 맞는 단어를 찾을 때까지 시도하는 횟수
+const MAX_ATTEMPTS: usize = 100;
+
+// 불러온 vocab으로 학습한 문자 단위 마르코프 체인
+// 발음은 가능하지만 실제로는 없는 단어를 만든다
+pub struct MarkovGenerator {
+    order: usize,
+    min_len: usize,
+    max_len: usize,
+    transitions: HashMap<Vec<char>, Vec<char>>,
+    known_words: HashSet<String>,
+}
+
+impl MarkovGenerator {
+    pub fn from_vocab(
+        vocab_generator: &VocabGenerator,
+        order: usize,
+        min_len: usize,
+        max_len: usize,
+    ) -> Self {
+        MarkovGenerator::new(&vocab_generator.get_words(), order, min_len, max_len)
+    }
+
+    pub fn new(words: &[String], order: usize, min_len: usize, max_len: usize) -> Self {
+        let order = order.max(1);
+        let mut transitions = HashMap::<Vec<char>, Vec<char>>::new();
+
+        for word in words {
+            let chars = std::iter::repeat_n(START, order)
+                .chain(word.chars())
+                .chain(std::iter::once(END))
+                .collect::<Vec<char>>();
+            for window in chars.windows(order + 1) {
+                transitions
+                    .entry(window[..order].to_vec())
+                    .or_default()
+                    .push(window[order]);
+            }
+        }
+
+        MarkovGenerator {
+            order,
+            min_len: min_len.max(1),
+            max_len: max_len.max(min_len.max(1)),
+            transitions,
+            known_words: words.iter().cloned().collect(),
+        }
+    }
+
+    fn walk(&self) -> String {
+        let mut rng = thread_rng();
+        let mut context = vec![START; self.order];
+        let mut word = String::new();
+
+        while word.chars().count() <= self.max_len {
+            let next = match self.transitions.get(&context) {
+                Some(candidates) => candidates[rng.gen_range(0, candidates.len())],
+                None => break,
+            };
+            if next == END {
+                break;
+            }
+            word.push(next);
+            context.remove(0);
+            context.push(next);
+        }
+        word
+    }
+}
+
+impl WordSource for MarkovGenerator {
+    fn generate(&mut self) -> VocabEntry {
+        let mut fallback = String::new();
+        for _ in 0..MAX_ATTEMPTS {
+            let word = self.walk();
+            let len = word.chars().count();
+            if len < self.min_len || len > self.max_len {
+                continue;
+            }
+            if !self.known_words.contains(&word) {
+                return VocabEntry::word(word);
+            }
+            fallback = word;
+        }
+        // 새 단어를 못 만들면 실제 단어라도 내보낸다
+        if fallback.is_empty() {
+            fallback = self.known_words.iter().next().cloned().unwrap_or_default();
+        }
+        VocabEntry::word(fallback)
+    }
+}
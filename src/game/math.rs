@@ -0,0 +1,74 @@
+use rand::{thread_rng, Rng};
+
+use super::source::WordSource;
+use super::vocab::VocabEntry;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MathDifficulty {
+    // 10 이하의 덧셈, 뺄셈
+    Easy,
+    // 구구단 범위의 곱셈과 50 이하의 덧셈, 뺄셈
+    Normal,
+    // 100 이하의 덧셈, 뺄셈과 20 이하의 곱셈, 나눗셈
+    Hard,
+}
+
+// 화면에는 `7*8` 같은 수식이 떨어지고, 플레이어는 계산 결과를 입력한다
+pub struct MathGenerator {
+    difficulty: MathDifficulty,
+}
+
+impl MathGenerator {
+    pub fn new(difficulty: MathDifficulty) -> Self {
+        MathGenerator { difficulty }
+    }
+
+    fn operators(&self) -> &'static [char] {
+        match self.difficulty {
+            MathDifficulty::Easy => &['+', '-'],
+            MathDifficulty::Normal => &['+', '-', '*'],
+            MathDifficulty::Hard => &['+', '-', '*', '/'],
+        }
+    }
+
+    // (덧셈/뺄셈 최대 피연산자, 곱셈/나눗셈 최대 피연산자)
+    fn operand_max(&self) -> (i32, i32) {
+        match self.difficulty {
+            MathDifficulty::Easy => (10, 0),
+            MathDifficulty::Normal => (50, 9),
+            MathDifficulty::Hard => (100, 20),
+        }
+    }
+}
+
+impl WordSource for MathGenerator {
+    fn generate(&mut self) -> VocabEntry {
+        let mut rng = thread_rng();
+        let operators = self.operators();
+        let operator = operators[rng.gen_range(0, operators.len())];
+        let (add_max, mul_max) = self.operand_max();
+
+        let (lhs, rhs, answer) = match operator {
+            '+' => {
+                let (lhs, rhs) = (rng.gen_range(1, add_max + 1), rng.gen_range(1, add_max + 1));
+                (lhs, rhs, lhs + rhs)
+            }
+            '-' => {
+                // 결과가 음수가 되지 않도록 큰 수를 앞에 둔다
+                let (a, b) = (rng.gen_range(1, add_max + 1), rng.gen_range(1, add_max + 1));
+                (a.max(b), a.min(b), a.max(b) - a.min(b))
+            }
+            '*' => {
+                let (lhs, rhs) = (rng.gen_range(2, mul_max + 1), rng.gen_range(2, mul_max + 1));
+                (lhs, rhs, lhs * rhs)
+            }
+            _ => {
+                // 나누어 떨어지는 나눗셈만 낸다
+                let (rhs, answer) = (rng.gen_range(2, mul_max + 1), rng.gen_range(2, mul_max + 1));
+                (rhs * answer, rhs, answer)
+            }
+        };
+
+        VocabEntry::new(format!("{}{}{}", lhs, operator, rhs), answer.to_string())
+    }
+}
@@ -1,3 +1,12 @@
+pub mod code;
+#[allow(clippy::module_inception)]
 pub mod game;
-mod vocab;
+pub mod markov;
+pub mod math;
+pub mod mode;
+pub mod passage;
+pub mod sentence;
+pub mod source;
+pub mod timeline;
+pub mod vocab;
 mod word;
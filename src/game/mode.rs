@@ -0,0 +1,322 @@
+use serde::Deserialize;
+use std::fs;
+use std::time::Duration;
+
+use crate::WordColor;
+
+// 게임 모드 파일이 있는 디렉토리
+const MODE_DIR: &str = "./config/modes";
+
+// TOML로 정의하는 게임 모드
+//
+//   name = "blitz"
+//
+//   [rules]
+//   lives = 3
+//   spawn_interval = 1.0
+//
+//   [rules.speed]
+//   base = 0.2
+//   per_score = 0.002
+//
+//   [[waves]]
+//   at = 10.0
+//   count = 5
+//
+// 적지 않은 항목은 기본 게임과 같은 값을 쓴다
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct GameMode {
+    pub name: String,
+    pub description: String,
+    pub rules: Rules,
+    pub waves: Vec<Wave>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Rules {
+    pub lives: i32,
+    // 단어가 떨어지는 간격 (초), 0이면 웨이브로만 떨어진다
+    pub spawn_interval: f32,
+    pub speed: SpeedCurve,
+    pub scoring: Scoring,
+    pub win: WinCondition,
+}
+
+// 프레임당 떨어지는 줄 수 = base + score * per_score (최대 max)
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SpeedCurve {
+    pub base: f32,
+    pub per_score: f32,
+    pub max: Option<f32>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Scoring {
+    // 맞힌 단어의 글자당 점수
+    pub per_char: i32,
+    // 놓친 단어의 글자당 감점
+    pub miss_per_char: i32,
+    // 공격 단어의 글자당 점수
+    pub attack_per_char: i32,
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct WinCondition {
+    // 이 점수에 도달하면 승리
+    pub score: Option<i32>,
+    // 이 시간(초) 동안 살아남으면 승리
+    pub survive: Option<f32>,
+    // 모든 웨이브를 내보내고 화면을 비우면 승리
+    pub clear_waves: bool,
+}
+
+// `at`초부터 `interval`초 간격으로 단어 `count`개를 떨어뜨린다
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Wave {
+    pub at: f32,
+    pub count: u32,
+    pub interval: f32,
+    pub speed: Option<f32>,
+    // "white" 또는 "red"
+    pub color: String,
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Rules {
+            lives: 5,
+            spawn_interval: 2.0,
+            speed: SpeedCurve::default(),
+            scoring: Scoring::default(),
+            win: WinCondition::default(),
+        }
+    }
+}
+
+impl Default for SpeedCurve {
+    fn default() -> Self {
+        SpeedCurve {
+            base: 0.2,
+            per_score: 0.001,
+            max: None,
+        }
+    }
+}
+
+impl Default for Scoring {
+    fn default() -> Self {
+        Scoring {
+            per_char: 1,
+            miss_per_char: 1,
+            attack_per_char: 1,
+        }
+    }
+}
+
+impl Default for Wave {
+    fn default() -> Self {
+        Wave {
+            at: 0.0,
+            count: 1,
+            interval: 0.5,
+            speed: None,
+            color: "white".to_string(),
+        }
+    }
+}
+
+impl SpeedCurve {
+    pub fn speed(&self, score: i32) -> f32 {
+        let speed = self.base + score as f32 * self.per_score;
+        match self.max {
+            Some(max) => speed.min(max),
+            None => speed,
+        }
+    }
+}
+
+impl Wave {
+    pub fn get_color(&self) -> WordColor {
+        match self.color.as_str() {
+            "red" => WordColor::Red,
+            _ => WordColor::White,
+        }
+    }
+}
+
+impl GameMode {
+    // ./config/modes/<name>.toml
+    pub fn path(name: &str) -> String {
+        format!("{}/{}.toml", MODE_DIR, name)
+    }
+
+    pub fn load(name: &str) -> Result<Self, String> {
+        let path = GameMode::path(name);
+        let contents = fs::read_to_string(&path).map_err(|e| format!("{}: {}", path, e))?;
+        GameMode::parse(&contents).map_err(|e| format!("{}: {}", path, e))
+    }
+
+    pub fn parse(contents: &str) -> Result<Self, String> {
+        let mode = toml::from_str::<GameMode>(contents).map_err(|e| e.to_string())?;
+        let rules = &mode.rules;
+        check_seconds("rules.spawn_interval", rules.spawn_interval)?;
+        check_speed("rules.speed.base", rules.speed.base)?;
+        check_finite("rules.speed.per_score", rules.speed.per_score)?;
+        if let Some(max) = rules.speed.max {
+            check_speed("rules.speed.max", max)?;
+        }
+        if let Some(survive) = rules.win.survive {
+            check_seconds("rules.win.survive", survive)?;
+        }
+        // 웨이브가 없으면 첫 프레임에 바로 승리하게 된다
+        if rules.win.clear_waves && mode.waves.is_empty() {
+            return Err("rules.win.clear_waves needs at least one [[waves]]".to_string());
+        }
+        for wave in &mode.waves {
+            if !matches!(wave.color.as_str(), "white" | "red") {
+                return Err(format!("unknown wave color: {}", wave.color));
+            }
+            check_seconds("waves.at", wave.at)?;
+            check_seconds("waves.interval", wave.interval)?;
+            if let Some(speed) = wave.speed {
+                check_speed("waves.speed", speed)?;
+            }
+        }
+        Ok(mode)
+    }
+
+    pub fn spawn_interval(&self) -> Option<Duration> {
+        if self.rules.spawn_interval > 0.0 {
+            Some(Duration::from_secs_f32(self.rules.spawn_interval))
+        } else {
+            None
+        }
+    }
+
+    // 웨이브를 (등장 시각, 속도, 색) 목록으로 펼친다
+    pub fn wave_spawns(&self) -> Vec<(Duration, Option<f32>, WordColor)> {
+        let mut spawns = Vec::new();
+        for wave in &self.waves {
+            for i in 0..wave.count {
+                let at = wave.at.max(0.0) + wave.interval.max(0.0) * i as f32;
+                spawns.push((Duration::from_secs_f32(at), wave.speed, wave.get_color()));
+            }
+        }
+        spawns.sort_by_key(|(at, _, _)| *at);
+        spawns
+    }
+}
+
+fn check_finite(field: &str, value: f32) -> Result<(), String> {
+    if value.is_finite() {
+        Ok(())
+    } else {
+        Err(format!("{} must be a finite number: {}", field, value))
+    }
+}
+
+// 속도가 0 이하면 단어가 바닥에 닿지 않는다
+fn check_speed(field: &str, value: f32) -> Result<(), String> {
+    check_finite(field, value)?;
+    if value <= 0.0 {
+        return Err(format!("{} must be positive: {}", field, value));
+    }
+    Ok(())
+}
+
+// 초 단위 값은 Duration으로 바꾸므로 0 이상의 유한한 값만 허용한다
+fn check_seconds(field: &str, value: f32) -> Result<(), String> {
+    check_finite(field, value)?;
+    if value < 0.0 {
+        return Err(format!("{} must not be negative: {}", field, value));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_mode_uses_defaults() {
+        let mode = GameMode::parse("").unwrap();
+        assert_eq!(mode.rules.lives, 5);
+        assert_eq!(mode.spawn_interval(), Some(Duration::from_secs(2)));
+        assert!(mode.wave_spawns().is_empty());
+    }
+
+    #[test]
+    fn zero_spawn_interval_means_waves_only() {
+        let mode = GameMode::parse("[rules]\nspawn_interval = 0.0").unwrap();
+        assert_eq!(mode.spawn_interval(), None);
+    }
+
+    #[test]
+    fn rejects_invalid_seconds() {
+        for contents in [
+            "[rules]\nspawn_interval = -1.0",
+            "[rules]\nspawn_interval = inf",
+            "[rules]\nspawn_interval = nan",
+            "[[waves]]\nat = -0.5",
+            "[[waves]]\nat = nan",
+            "[[waves]]\ninterval = inf",
+            "[rules.win]\nsurvive = -3.0",
+        ] {
+            assert!(GameMode::parse(contents).is_err(), "{}", contents);
+        }
+    }
+
+    #[test]
+    fn rejects_non_finite_speed() {
+        assert!(GameMode::parse("[rules.speed]\nbase = nan").is_err());
+        assert!(GameMode::parse("[rules.speed]\nmax = inf").is_err());
+        assert!(GameMode::parse("[[waves]]\nspeed = nan").is_err());
+    }
+
+    #[test]
+    fn rejects_non_positive_speed() {
+        assert!(GameMode::parse("[rules.speed]\nbase = 0.0").is_err());
+        assert!(GameMode::parse("[rules.speed]\nmax = -0.5").is_err());
+        assert!(GameMode::parse("[[waves]]\nspeed = -1.0").is_err());
+        assert!(GameMode::parse("[[waves]]\nspeed = 0.2").is_ok());
+    }
+
+    #[test]
+    fn clear_waves_needs_waves() {
+        assert!(GameMode::parse("[rules.win]\nclear_waves = true").is_err());
+        let contents = "[rules.win]\nclear_waves = true\n\n[[waves]]\nat = 1.0";
+        assert!(GameMode::parse(contents).is_ok());
+    }
+
+    #[test]
+    fn rejects_unknown_fields_and_colors() {
+        assert!(GameMode::parse("[rules]\nlifes = 3").is_err());
+        assert!(GameMode::parse("[[waves]]\ncolor = \"blue\"").is_err());
+    }
+
+    #[test]
+    fn wave_spawns_are_sorted() {
+        let contents = "[[waves]]\nat = 5.0\ncount = 2\ninterval = 1.0\n\n[[waves]]\nat = 1.0\ncolor = \"red\"";
+        let spawns = GameMode::parse(contents).unwrap().wave_spawns();
+        let times = spawns
+            .iter()
+            .map(|(at, _, _)| at.as_secs_f32())
+            .collect::<Vec<f32>>();
+        assert_eq!(times, vec![1.0, 5.0, 6.0]);
+        assert_eq!(spawns[0].2, WordColor::Red);
+    }
+
+    #[test]
+    fn bundled_modes_parse() {
+        for name in ["classic", "blitz", "storm", "survival"] {
+            let contents = fs::read_to_string(GameMode::path(name)).unwrap();
+            assert!(GameMode::parse(&contents).is_ok(), "{}", name);
+        }
+    }
+}
@@ -0,0 +1,152 @@
+use rand::{thread_rng, Rng};
+use std::collections::VecDeque;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::source::WordSource;
+use super::vocab::VocabEntry;
+
+// 텍스트 파일의 단어를 읽는 순서대로 내보낸다
+// 단어를 입력할 때마다 아직 입력하지 못한 가장 앞 단어를 저장해 두었다가 다음에 이어서 시작한다
+// (Ctrl-C로 끝내도 진행도가 남도록 게임이 끝날 때가 아니라 그때그때 저장한다)
+pub struct PassageGenerator {
+    key: String,
+    words: Vec<String>,
+    position: usize,
+    // 내보냈지만 아직 입력하지 못한 단어의 위치 (떨어지는 중이거나 놓친 단어)
+    pending: VecDeque<usize>,
+    // 마지막으로 저장한 책갈피 위치
+    bookmark: usize,
+}
+
+impl PassageGenerator {
+    pub fn new(path: &str, resume: bool) -> Result<Self, String> {
+        let contents = fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?;
+        let words = contents
+            .split_whitespace()
+            .map(|word| word.to_string())
+            .collect::<Vec<String>>();
+
+        // 같은 파일은 어디서 실행해도 같은 책갈피를 쓰도록 절대 경로를 키로 사용한다
+        let key = fs::canonicalize(path)
+            .map(|path| path.display().to_string())
+            .unwrap_or_else(|_| path.to_string());
+
+        let position = if resume {
+            load_bookmark(&key)
+                .filter(|position| *position < words.len())
+                .unwrap_or(0)
+        } else {
+            0
+        };
+
+        Ok(PassageGenerator {
+            key,
+            words,
+            position,
+            pending: VecDeque::new(),
+            bookmark: position,
+        })
+    }
+
+    // 입력하지 못한 단어부터 다시 시작하고, 끝까지 다 입력했으면 처음부터 시작한다
+    fn resume_position(&self) -> usize {
+        match self.pending.front() {
+            Some(&position) => position,
+            None if self.is_finished() => 0,
+            None => self.position,
+        }
+    }
+}
+
+impl WordSource for PassageGenerator {
+    fn generate(&mut self) -> VocabEntry {
+        match self.words.get(self.position) {
+            Some(word) => {
+                self.pending.push_back(self.position);
+                self.position += 1;
+                VocabEntry::word(word.clone())
+            }
+            None => VocabEntry::default(),
+        }
+    }
+
+    // 같은 단어가 여러 번 나오면 가장 앞의 것을 입력한 것으로 본다
+    fn complete(&mut self, answer: &str) {
+        if let Some(index) = self
+            .pending
+            .iter()
+            .position(|&position| self.words[position] == answer)
+        {
+            self.pending.remove(index);
+        }
+        let position = self.resume_position();
+        if position != self.bookmark {
+            save_bookmark(&self.key, position);
+            self.bookmark = position;
+        }
+    }
+
+    // 공격 단어는 본문 순서를 건드리지 않도록 아무 단어나 고른다
+    fn generate_attack(&mut self) -> VocabEntry {
+        if self.words.is_empty() {
+            return VocabEntry::default();
+        }
+        let mut rng = thread_rng();
+        VocabEntry::word(self.words[rng.gen_range(0, self.words.len())].clone())
+    }
+
+    fn is_finished(&self) -> bool {
+        self.position >= self.words.len()
+    }
+
+    fn progress(&self) -> Option<(usize, usize)> {
+        Some((self.position, self.words.len()))
+    }
+}
+
+fn bookmark_path() -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| dir.join("raingame").join("bookmarks.txt"))
+}
+
+// 책갈피 파일은 `position<TAB>path` 형식의 줄로 이루어진다
+fn read_bookmarks(path: &Path) -> Vec<(usize, String)> {
+    fs::read_to_string(path)
+        .unwrap_or_default()
+        .lines()
+        .filter_map(|line| {
+            let (position, key) = line.split_once('\t')?;
+            Some((position.parse().ok()?, key.to_string()))
+        })
+        .collect()
+}
+
+fn load_bookmark(key: &str) -> Option<usize> {
+    let path = bookmark_path()?;
+    read_bookmarks(&path)
+        .into_iter()
+        .find(|(_, bookmark_key)| bookmark_key == key)
+        .map(|(position, _)| position)
+}
+
+fn save_bookmark(key: &str, position: usize) {
+    let path = match bookmark_path() {
+        Some(path) => path,
+        None => return,
+    };
+
+    let mut bookmarks = read_bookmarks(&path);
+    bookmarks.retain(|(_, bookmark_key)| bookmark_key != key);
+    if position > 0 {
+        bookmarks.push((position, key.to_string()));
+    }
+
+    let contents = bookmarks
+        .iter()
+        .map(|(position, key)| format!("{}\t{}\n", position, key))
+        .collect::<String>();
+    if let Some(dir) = path.parent() {
+        _ = fs::create_dir_all(dir);
+    }
+    _ = fs::write(path, contents);
+}
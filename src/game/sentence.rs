@@ -0,0 +1,46 @@
+use rand::{thread_rng, Rng};
+
+use super::source::WordSource;
+use super::vocab::{VocabEntry, VocabGenerator};
+
+// 단어 여러 개를 이어 붙인 문장 조각
+pub struct SentenceGenerator {
+    vocab_generator: VocabGenerator,
+    min_words: usize,
+    max_words: usize,
+    max_len: usize,
+}
+
+impl SentenceGenerator {
+    pub fn new(
+        vocab_generator: VocabGenerator,
+        min_words: usize,
+        max_words: usize,
+        max_len: usize,
+    ) -> Self {
+        SentenceGenerator {
+            vocab_generator,
+            min_words: min_words.max(1),
+            max_words: max_words.max(min_words.max(1)),
+            max_len,
+        }
+    }
+}
+
+impl WordSource for SentenceGenerator {
+    fn generate(&mut self) -> VocabEntry {
+        let mut rng = thread_rng();
+        let count = rng.gen_range(self.min_words, self.max_words + 1);
+
+        let mut sentence = self.vocab_generator.generate().get_answer().clone();
+        for _ in 1..count {
+            let word = self.vocab_generator.generate();
+            if sentence.chars().count() + 1 + word.get_answer().chars().count() > self.max_len {
+                break;
+            }
+            sentence.push(' ');
+            sentence.push_str(word.get_answer());
+        }
+        VocabEntry::word(sentence)
+    }
+}
@@ -0,0 +1,30 @@
+use super::vocab::VocabEntry;
+
+// Game에 떨어질 단어를 공급하는 생성기
+// 라이브러리 사용자는 이 trait을 구현해서 자신만의 단어 생성기를 붙일 수 있다
+pub trait WordSource: Send {
+    fn generate(&mut self) -> VocabEntry;
+
+    // 공격 단어(ATTACK, 빨간 단어)용 생성
+    fn generate_attack(&mut self) -> VocabEntry {
+        self.generate()
+    }
+
+    // 내보낸 단어를 플레이어가 입력했을 때 호출된다
+    fn complete(&mut self, _answer: &str) {}
+
+    // 더 내보낼 단어가 없는지
+    fn is_finished(&self) -> bool {
+        false
+    }
+
+    // (진행한 단어 수, 전체 단어 수)
+    fn progress(&self) -> Option<(usize, usize)> {
+        None
+    }
+
+    // 대소문자, 공백, 문장 부호까지 정확히 일치해야 하는지
+    fn is_strict(&self) -> bool {
+        false
+    }
+}
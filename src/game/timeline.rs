@@ -0,0 +1,197 @@
+use std::fs;
+use std::time::Duration;
+
+use super::vocab::VocabEntry;
+
+// 정해진 시각에 떨어질 단어 하나
+#[derive(Clone)]
+pub struct TimelineEvent {
+    time: Duration,
+    vocab: VocabEntry,
+    column: Option<i32>,
+    speed: Option<f32>,
+}
+
+impl TimelineEvent {
+    pub fn get_time(&self) -> Duration {
+        self.time
+    }
+
+    pub fn get_vocab(&self) -> &VocabEntry {
+        &self.vocab
+    }
+
+    pub fn get_column(&self) -> Option<i32> {
+        self.column
+    }
+
+    pub fn get_speed(&self) -> Option<f32> {
+        self.speed
+    }
+}
+
+// LRC 형식을 확장한 단어 등장 시간표
+//
+//   # 주석
+//   [00:01.50] hello
+//   [00:03.00][x:10] world
+//   [00:04.25][x:30][speed:0.3] rain drops keep falling
+//
+// 첫 태그는 등장 시각(mm:ss.xx), 이어지는 태그로 열(x)과 속도(speed, 프레임당 줄 수)를 지정한다
+pub struct Timeline {
+    events: Vec<TimelineEvent>,
+    next: usize,
+}
+
+impl Timeline {
+    pub fn from_file(path: &str) -> Result<Self, String> {
+        let contents = fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?;
+        Timeline::parse(&contents).map_err(|e| format!("{}:{}", path, e))
+    }
+
+    pub fn parse(contents: &str) -> Result<Self, String> {
+        let mut events = Vec::new();
+        for (index, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let event = parse_line(line).map_err(|e| format!("{}: {}", index + 1, e))?;
+            events.push(event);
+        }
+        // 시간 순서대로 정렬 (같은 시각은 파일 순서 유지)
+        events.sort_by_key(|event| event.time);
+
+        Ok(Timeline { events, next: 0 })
+    }
+
+    // 지금까지 등장해야 하는 단어를 꺼낸다
+    pub fn pop_due(&mut self, elapsed: Duration) -> Vec<TimelineEvent> {
+        let start = self.next;
+        while self.next < self.events.len() && self.events[self.next].time <= elapsed {
+            self.next += 1;
+        }
+        self.events[start..self.next].to_vec()
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.next >= self.events.len()
+    }
+
+    pub fn progress(&self) -> (usize, usize) {
+        (self.next, self.events.len())
+    }
+}
+
+fn parse_line(line: &str) -> Result<TimelineEvent, String> {
+    let mut rest = line;
+    let mut tags = Vec::new();
+    while let Some(tagged) = rest.strip_prefix('[') {
+        let end = tagged.find(']').ok_or_else(|| "unclosed tag".to_string())?;
+        tags.push(&tagged[..end]);
+        rest = &tagged[end + 1..];
+    }
+
+    let (time_tag, option_tags) = tags
+        .split_first()
+        .ok_or_else(|| "missing [mm:ss.xx] time tag".to_string())?;
+    let time = parse_time(time_tag)?;
+
+    let mut column = None;
+    let mut speed = None;
+    for tag in option_tags {
+        match tag.split_once(':') {
+            Some(("x", value)) => {
+                column = Some(
+                    value
+                        .trim()
+                        .parse()
+                        .map_err(|_| format!("invalid column: {}", value))?,
+                );
+            }
+            Some(("speed", value)) => {
+                // 0 이하나 nan이면 단어가 바닥에 닿지 않아 끝낼 수 없다
+                match value.trim().parse::<f32>() {
+                    Ok(value) if value.is_finite() && value > 0.0 => speed = Some(value),
+                    _ => return Err(format!("invalid speed: {}", value)),
+                }
+            }
+            _ => return Err(format!("unknown tag: [{}]", tag)),
+        }
+    }
+
+    let vocab = VocabEntry::parse(rest.trim()).ok_or_else(|| "missing word".to_string())?;
+
+    Ok(TimelineEvent {
+        time,
+        vocab,
+        column,
+        speed,
+    })
+}
+
+// mm:ss.xx
+fn parse_time(tag: &str) -> Result<Duration, String> {
+    let invalid = || format!("invalid time: [{}]", tag);
+    let (minutes, seconds) = tag.split_once(':').ok_or_else(invalid)?;
+    let minutes = minutes.trim().parse::<u64>().map_err(|_| invalid())?;
+    let seconds = seconds.trim().parse::<f64>().map_err(|_| invalid())?;
+    if !(0.0..60.0).contains(&seconds) {
+        return Err(invalid());
+    }
+    minutes
+        .checked_mul(60)
+        .and_then(|minutes| {
+            Duration::from_secs(minutes).checked_add(Duration::from_secs_f64(seconds))
+        })
+        .ok_or_else(invalid)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_time_and_options() {
+        let timeline = Timeline::parse("# 주석\n\n[01:02.50][x:10][speed:0.3] hello\n").unwrap();
+        let event = &timeline.events[0];
+        assert_eq!(event.get_time(), Duration::from_millis(62_500));
+        assert_eq!(event.get_column(), Some(10));
+        assert_eq!(event.get_speed(), Some(0.3));
+        assert_eq!(event.get_vocab().get_prompt(), "hello");
+    }
+
+    #[test]
+    fn pops_events_in_time_order() {
+        let mut timeline = Timeline::parse("[00:03.00] b\n[00:01.00] a\n").unwrap();
+        assert!(timeline.pop_due(Duration::ZERO).is_empty());
+        let due = timeline.pop_due(Duration::from_secs(2));
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].get_vocab().get_prompt(), "a");
+        assert_eq!(timeline.pop_due(Duration::from_secs(3)).len(), 1);
+        assert!(timeline.is_finished());
+    }
+
+    #[test]
+    fn rejects_invalid_speed() {
+        for speed in ["nan", "inf", "-1", "0", "fast"] {
+            let line = format!("[00:01.00][speed:{}] word", speed);
+            assert!(Timeline::parse(&line).is_err(), "{}", speed);
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_time() {
+        assert!(Timeline::parse("[00:60.00] word").is_err());
+        assert!(Timeline::parse("[1.5] word").is_err());
+        assert!(Timeline::parse("[18446744073709551615:00.00] word").is_err());
+    }
+
+    #[test]
+    fn rejects_malformed_lines() {
+        assert!(Timeline::parse("[00:01.00 word").is_err());
+        assert!(Timeline::parse("[00:01.00]").is_err());
+        assert!(Timeline::parse("[00:01.00][y:3] word").is_err());
+        assert!(Timeline::parse("word").is_err());
+    }
+}
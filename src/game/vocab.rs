@@ -1,32 +1,105 @@
 use rand::{thread_rng, Rng};
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use sha2::{Digest, Sha256};
+use std::fs;
+
+use super::source::WordSource;
+
+// 화면에 보이는 문제(prompt)와 입력해야 하는 정답(answer) 한 쌍
+// 일반 단어는 prompt와 answer가 같다
+#[derive(Clone, Debug, Default)]
+pub struct VocabEntry {
+    prompt: String,
+    answer: String,
+}
+
+impl VocabEntry {
+    pub fn new(prompt: String, answer: String) -> Self {
+        VocabEntry { prompt, answer }
+    }
+
+    pub fn word(text: String) -> Self {
+        VocabEntry {
+            prompt: text.clone(),
+            answer: text,
+        }
+    }
+
+    // `prompt\tanswer` 형식이면 단어 쌍, 아니면 일반 단어
+    pub fn parse(line: &str) -> Option<Self> {
+        let line = line.trim_end_matches(['\r', '\n']);
+        if line.trim().is_empty() {
+            return None;
+        }
+        match line.split_once('\t') {
+            Some((prompt, answer)) => Some(VocabEntry::new(
+                prompt.trim().to_string(),
+                answer.trim().to_string(),
+            )),
+            None => Some(VocabEntry::word(line.to_string())),
+        }
+    }
+
+    pub fn get_prompt(&self) -> &String {
+        &self.prompt
+    }
+
+    pub fn get_answer(&self) -> &String {
+        &self.answer
+    }
+}
 
 pub struct VocabGenerator {
-    vocab: Vec<String>,
+    vocab: Vec<VocabEntry>,
 }
 
 impl VocabGenerator {
     pub fn new() -> Self {
-        VocabGenerator {
-            vocab: {
-                let file = File::open("./vocab.txt").unwrap();
-                let reader = BufReader::new(file);
-
-                let mut vocabs = Vec::<String>::new();
-                for line in reader.lines() {
-                    if let Ok(vocab) = line {
-                        vocabs.push(vocab);
-                    }
-                }
-                vocabs
-            },
+        VocabGenerator::from_file("./config/vocab.txt").expect("Need ./config/vocab.txt for game")
+    }
+
+    pub fn from_file(path: &str) -> Result<Self, String> {
+        let contents = fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?;
+        VocabGenerator::from_contents(&contents).map_err(|e| format!("{}: {}", path, e))
+    }
+
+    // 서버에서 받은 vocab처럼 이미 메모리에 있는 내용으로 만든다
+    // 단어가 하나도 없으면 뽑을 수 없으므로 거절한다
+    pub fn from_contents(contents: &str) -> Result<Self, String> {
+        let vocab = contents
+            .lines()
+            .filter_map(VocabEntry::parse)
+            .collect::<Vec<VocabEntry>>();
+        if vocab.is_empty() {
+            return Err("no words".to_string());
         }
+        Ok(VocabGenerator { vocab })
+    }
+
+    pub fn get_words(&self) -> Vec<String> {
+        self.vocab
+            .iter()
+            .map(|entry| entry.get_answer().clone())
+            .collect()
     }
+}
 
-    pub fn generate(&mut self) -> String {
+impl WordSource for VocabGenerator {
+    fn generate(&mut self) -> VocabEntry {
         let mut rng = thread_rng();
-        let random_vocab = self.vocab[rng.gen_range(0, self.vocab.len())].clone();
-        random_vocab
+        self.vocab[rng.gen_range(0, self.vocab.len())].clone()
     }
 }
+
+impl Default for VocabGenerator {
+    fn default() -> Self {
+        VocabGenerator::new()
+    }
+}
+
+// vocab 내용의 SHA-256 해시 (16진수 문자열)
+pub fn vocab_hash(contents: &[u8]) -> String {
+    Sha256::digest(contents)
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
@@ -1,15 +1,41 @@
+use rand::seq::SliceRandom;
+
 use crate::WordColor;
 
 pub struct Word {
     x: f32,
     y: f32,
     text: String,
+    answer: String,
     color: WordColor,
+    speed: Option<f32>,
+    scrambled: Option<String>,
 }
 
 impl Word {
     pub fn new(x: f32, y: f32, text: String, color: WordColor) -> Self {
-        Word { x, y, text, color }
+        let answer = text.clone();
+        Word {
+            x,
+            y,
+            text,
+            answer,
+            color,
+            speed: None,
+            scrambled: None,
+        }
+    }
+
+    pub fn with_answer(x: f32, y: f32, text: String, answer: String, color: WordColor) -> Self {
+        Word {
+            x,
+            y,
+            text,
+            answer,
+            color,
+            speed: None,
+            scrambled: None,
+        }
     }
 
     pub fn get_x(&self) -> f32 {
@@ -27,6 +53,34 @@ impl Word {
     pub fn get_text(&self) -> &String {
         &self.text
     }
+
+    pub fn get_answer(&self) -> &String {
+        &self.answer
+    }
+
+    // 지정된 경우 점수에 따른 속도 대신 이 속도(프레임당 줄 수)로 떨어진다
+    pub fn get_speed(&self) -> Option<f32> {
+        self.speed
+    }
+
+    pub fn set_speed(&mut self, speed: Option<f32>) {
+        self.speed = speed;
+    }
+
+    // 화면에 보이는 글자만 섞는다 (정답은 그대로)
+    pub fn scramble(&mut self) {
+        let mut chars = self.text.chars().collect::<Vec<char>>();
+        chars.shuffle(&mut rand::thread_rng());
+        self.scrambled = Some(chars.into_iter().collect());
+    }
+
+    pub fn unscramble(&mut self) {
+        self.scrambled = None;
+    }
+
+    pub fn get_display_text(&self) -> &String {
+        self.scrambled.as_ref().unwrap_or(&self.text)
+    }
 }
 
 impl Word {
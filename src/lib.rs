@@ -1,5 +1,21 @@
 mod enums;
 mod game;
+mod protocol;
 
-pub use enums::{GameState, Message, WordColor};
-pub use game::game::{play, Game};
+pub use enums::{
+    AttackKind, Board, BoardWord, GameState, Message, Rating, RoomInfo, Target, WordColor,
+};
+pub use game::code::CodeGenerator;
+pub use game::game::{play, play_with, Game, MAX_ATTACK_STRENGTH};
+pub use game::markov::MarkovGenerator;
+pub use game::math::{MathDifficulty, MathGenerator};
+pub use game::mode::GameMode;
+pub use game::passage::PassageGenerator;
+pub use game::sentence::SentenceGenerator;
+pub use game::source::WordSource;
+pub use game::timeline::Timeline;
+pub use game::vocab::{vocab_hash, VocabEntry, VocabGenerator};
+pub use protocol::{
+    parse_seconds, read_message, spawn_message_reader, write_message, ProtocolError,
+    PROTOCOL_VERSION,
+};
@@ -0,0 +1,237 @@
+use std::fmt;
+use std::io;
+use std::time::Duration;
+
+use tokio::{
+    io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt},
+    sync::mpsc::{self, Receiver},
+};
+
+use crate::Message;
+
+// 호환되지 않는 클라이언트/서버는 핸드셰이크에서 걸러진다
+pub const PROTOCOL_VERSION: u16 = 1;
+
+// Hello 메세지로 raingame 클라이언트인지 확인한다
+const MAGIC: &str = "RAIN";
+
+// 메세지 하나의 최대 크기 (vocab 전송 포함)
+const MAX_FRAME_LEN: u32 = 16 * 1024 * 1024;
+
+#[derive(Debug)]
+pub enum ProtocolError {
+    Io(io::Error),
+    EmptyFrame,
+    FrameTooLarge(u32),
+    Decode(String),
+    Unexpected(&'static str),
+    Incompatible(String),
+    Closed,
+}
+
+impl fmt::Display for ProtocolError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProtocolError::Io(e) => write!(f, "I/O error: {}", e),
+            ProtocolError::EmptyFrame => write!(f, "empty frame"),
+            ProtocolError::FrameTooLarge(len) => write!(f, "frame too large: {} bytes", len),
+            ProtocolError::Decode(e) => write!(f, "invalid message: {}", e),
+            ProtocolError::Unexpected(kind) => write!(f, "unexpected message: {}", kind),
+            ProtocolError::Incompatible(reason) => write!(f, "incompatible peer: {}", reason),
+            ProtocolError::Closed => write!(f, "connection closed"),
+        }
+    }
+}
+
+impl ProtocolError {
+    // 프레임 하나를 통째로 읽은 뒤의 오류라서 그 메세지만 버리고 계속 읽을 수 있는지
+    // (모르는 메세지나 더 새로운 버전의 메세지 하나 때문에 연결을 끊지 않는다)
+    pub fn is_recoverable(&self) -> bool {
+        matches!(self, ProtocolError::Decode(_) | ProtocolError::EmptyFrame)
+    }
+}
+
+impl From<io::Error> for ProtocolError {
+    fn from(e: io::Error) -> Self {
+        if e.kind() == io::ErrorKind::UnexpectedEof {
+            ProtocolError::Closed
+        } else {
+            ProtocolError::Io(e)
+        }
+    }
+}
+
+impl Message {
+    pub fn hello(name: &str) -> Self {
+        Message::Hello {
+            magic: MAGIC.to_string(),
+            version: PROTOCOL_VERSION,
+            name: name.to_string(),
+            token: None,
+        }
+    }
+
+    // 세션 토큰으로 진행 중인 게임에 다시 접속한다
+    pub fn resume(name: &str, token: &str) -> Self {
+        Message::Hello {
+            magic: MAGIC.to_string(),
+            version: PROTOCOL_VERSION,
+            name: name.to_string(),
+            token: Some(token.to_string()),
+        }
+    }
+
+    // 받은 Hello 메세지의 매직 넘버와 버전을 확인하고 플레이어 이름과 세션 토큰을 돌려준다
+    pub fn check_hello(&self) -> Result<(String, Option<String>), ProtocolError> {
+        let (magic, version, name, token) = match self {
+            Message::Hello {
+                magic,
+                version,
+                name,
+                token,
+            } => (magic, *version, name, token),
+            _ => return Err(ProtocolError::Unexpected(self.kind())),
+        };
+        if magic != MAGIC {
+            return Err(ProtocolError::Incompatible(
+                "not a raingame client".to_string(),
+            ));
+        }
+        if version != PROTOCOL_VERSION {
+            return Err(ProtocolError::Incompatible(format!(
+                "protocol version {} (server speaks {})",
+                version, PROTOCOL_VERSION
+            )));
+        }
+        Ok((name.clone(), token.clone()))
+    }
+}
+
+// 핑 간격, 타임아웃 같은 초 단위 커맨드라인 옵션 (0보다 크고 Duration으로 나타낼 수 있는 값만 받는다)
+pub fn parse_seconds(value: &str) -> Result<Duration, String> {
+    let seconds = value.parse::<f32>().map_err(|e| e.to_string())?;
+    match Duration::try_from_secs_f32(seconds) {
+        Ok(duration) if !duration.is_zero() => Ok(duration),
+        _ => Err(format!("must be a positive number of seconds: {}", value)),
+    }
+}
+
+// 메세지 하나: u32 길이 + JSON 본문
+pub async fn read_message<R: AsyncRead + Unpin>(reader: &mut R) -> Result<Message, ProtocolError> {
+    let len = reader.read_u32().await?;
+    if len == 0 {
+        return Err(ProtocolError::EmptyFrame);
+    }
+    if len > MAX_FRAME_LEN {
+        return Err(ProtocolError::FrameTooLarge(len));
+    }
+
+    let mut body = vec![0; len as usize];
+    reader.read_exact(&mut body).await?;
+
+    serde_json::from_slice(&body).map_err(|e| ProtocolError::Decode(e.to_string()))
+}
+
+pub async fn write_message<W: AsyncWrite + Unpin>(
+    writer: &mut W,
+    message: &Message,
+) -> Result<(), ProtocolError> {
+    let body = serde_json::to_vec(message).expect("Message should be serializable");
+    let len = body.len() as u32;
+    if len > MAX_FRAME_LEN {
+        return Err(ProtocolError::FrameTooLarge(len));
+    }
+
+    let mut buf = Vec::with_capacity(body.len() + 4);
+    buf.extend_from_slice(&len.to_be_bytes());
+    buf.extend_from_slice(&body);
+    writer.write_all(&buf).await?;
+    Ok(())
+}
+
+// 소켓 읽기를 별도 태스크로 분리한다
+// read_message는 select!에서 취소되면 메세지 중간에 끊길 수 있으므로 채널로 받는다
+// 오류는 받는 쪽에 전달하고, 다시 읽을 수 없는 오류(I/O, 연결 끊김)면 종료한다
+pub fn spawn_message_reader<R: AsyncRead + Unpin + Send + 'static>(
+    mut reader: R,
+) -> Receiver<Result<Message, ProtocolError>> {
+    let (message_writer, message_reader) = mpsc::channel(8);
+    tokio::spawn(async move {
+        loop {
+            let message = read_message(&mut reader).await;
+            let failed = matches!(&message, Err(e) if !e.is_recoverable());
+            if message_writer.send(message).await.is_err() || failed {
+                break;
+            }
+        }
+    });
+    message_reader
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::duplex;
+
+    #[tokio::test]
+    async fn round_trips_through_write_message() {
+        let (mut client, mut server) = duplex(1024);
+        write_message(&mut client, &Message::hello("rain"))
+            .await
+            .unwrap();
+        let hello = read_message(&mut server).await.unwrap();
+        assert_eq!(hello.check_hello().unwrap(), ("rain".to_string(), None));
+    }
+
+    #[tokio::test]
+    async fn rejects_empty_and_oversized_frames() {
+        let (mut client, mut server) = duplex(1024);
+        client.write_all(&0u32.to_be_bytes()).await.unwrap();
+        client
+            .write_all(&(MAX_FRAME_LEN + 1).to_be_bytes())
+            .await
+            .unwrap();
+        assert!(matches!(
+            read_message(&mut server).await,
+            Err(ProtocolError::EmptyFrame)
+        ));
+        assert!(matches!(
+            read_message(&mut server).await,
+            Err(ProtocolError::FrameTooLarge(len)) if len == MAX_FRAME_LEN + 1
+        ));
+    }
+
+    #[tokio::test]
+    async fn reader_skips_undecodable_frame() {
+        let (mut client, server) = duplex(1024);
+        let body = b"{\"NoSuchMessage\":{}}";
+        client
+            .write_all(&(body.len() as u32).to_be_bytes())
+            .await
+            .unwrap();
+        client.write_all(body).await.unwrap();
+        write_message(&mut client, &Message::hello("rain"))
+            .await
+            .unwrap();
+
+        let mut messages = spawn_message_reader(server);
+        let skipped = messages.recv().await.unwrap();
+        assert!(matches!(&skipped, Err(e) if e.is_recoverable()));
+        assert!(matches!(
+            messages.recv().await,
+            Some(Ok(Message::Hello { .. }))
+        ));
+    }
+
+    #[tokio::test]
+    async fn reader_stops_after_connection_closes() {
+        let (client, server) = duplex(1024);
+        drop(client);
+        let mut messages = spawn_message_reader(server);
+        assert!(matches!(
+            messages.recv().await,
+            Some(Err(ProtocolError::Closed))
+        ));
+        assert!(messages.recv().await.is_none());
+    }
+}